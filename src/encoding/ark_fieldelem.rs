@@ -0,0 +1,139 @@
+/// The number of bytes in a serialized arkworks scalar
+pub(super) const SCALAR_SIZE: usize = 32;
+
+/// Implements `AttributeEncoder` and `AttributeDecoder` for an arkworks
+/// scalar field whose order is above 2^252 and below 2^254.
+/// Zero is centered at 2^251 which is below the field order so it is never
+/// reduced and there are always 250 bits below it.
+/// `$max_positive` is the most bits that fit between 2^251 and +infinity
+macro_rules! ark_field_encoder {
+    ($field:ty, $(#[$doc:meta])* MAX_POSITIVE_BITS = $max_positive:expr) => {
+        impl AttributeEncoder for $field {
+            type Output = $field;
+
+            /// The field order is below 2^254 so zero is centered at 2^251
+            /// where it is not reduced
+            const BITS_IN_ZERO: usize = 251;
+            $(#[$doc])*
+            const MAX_POSITIVE_BITS: usize = $max_positive;
+
+            fn max() -> Self::Output {
+                <$field as ark_ff::Zero>::zero()
+            }
+
+            fn zero_center() -> Self::Output {
+                ark_ff::Field::pow(&<$field>::from(2u64), [Self::BITS_IN_ZERO as u64])
+            }
+
+            fn from_vec(bytes: Vec<u8>) -> Result<Self::Output, Error> {
+                if bytes.len() > $crate::encoding::ark_fieldelem::SCALAR_SIZE {
+                    return Err(Error::new(ErrorKind::OutOfRange, format!("Expected at most {} bytes, found {}", $crate::encoding::ark_fieldelem::SCALAR_SIZE, bytes.len())));
+                }
+                Ok(ark_ff::PrimeField::from_be_bytes_mod_order(bytes.as_slice()))
+            }
+        }
+
+        impl AttributeDecoder for $field {
+            fn to_vec(value: &Self::Output) -> Vec<u8> {
+                ark_ff::BigInteger::to_bytes_be(&ark_ff::PrimeField::into_bigint(*value))
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::encoding::AttributeDecoder;
+    use crate::errors::ErrorKind;
+    use ark_ff::PrimeField;
+
+    /// Call `$check::<F>($args)` for every enabled arkworks field `F`
+    macro_rules! for_each_field {
+        ($check:ident($($arg:expr),*)) => {{
+            #[cfg(feature = "bls377")]
+            $check::<ark_bls12_377::Fr>($($arg),*);
+            #[cfg(feature = "bn254")]
+            $check::<ark_bn254::Fr>($($arg),*);
+        }};
+    }
+
+    fn rfc3339_string_convert<F: PrimeField + AttributeDecoder<Output = F>>() {
+        let res = F::encode_from_rfc3339_as_unixtimestamp("2018-01-26T18:30:09.453+00:00");
+        assert_eq!(F::from(1_516_991_409u64) + F::zero_center(), res.unwrap());
+
+        let res = F::encode_from_rfc3339_as_unixtimestamp("1970-01-01T00:00:00.000+00:00");
+        assert_eq!(res.unwrap(), F::zero_center());
+
+        let res = F::encode_from_rfc3339_as_unixtimestamp("1900");
+        assert_eq!(ErrorKind::InvalidDate, res.err().unwrap().kind());
+
+        let res = F::encode_from_rfc3339_as_dayssince1900("1982-12-20T10:45:00.000-06:00").unwrap();
+        assert_eq!(res, F::zero_center() + F::from(30303u64));
+        assert_eq!(chrono::NaiveDate::from_ymd_opt(1982, 12, 20).unwrap(), F::decode_dayssince1900_to_date(&res).unwrap());
+    }
+
+    #[test]
+    fn rfc3339_string_convert_test() {
+        for_each_field!(rfc3339_string_convert());
+    }
+
+    fn zero_center<F: PrimeField + AttributeDecoder<Output = F>>() {
+        let mut expected = vec![0u8; 32];
+        expected[0] = 0x08;
+        assert_eq!(expected, F::to_vec(&F::zero_center()));
+    }
+
+    #[test]
+    fn zero_center_test() {
+        for_each_field!(zero_center());
+    }
+
+    fn decimal<F: PrimeField + AttributeDecoder<Output = F>>() {
+        let res1 = F::encode_from_f64(1.33f32).unwrap();
+        let res2 = F::encode_from_f64(-1.33f32).unwrap();
+        assert_eq!(F::zero_center() + F::zero_center(), res1 + res2);
+
+        assert_eq!(F::from(8u64), F::encode_from_f64(f64::NEG_INFINITY).unwrap());
+        assert_eq!(-F::from(9u64), F::encode_from_f64(f64::INFINITY).unwrap());
+        assert_eq!(-F::from(8u64), F::encode_from_f64(f64::NAN).unwrap());
+    }
+
+    #[test]
+    fn decimal_test() {
+        for_each_field!(decimal());
+    }
+
+    fn fixed_point<F: PrimeField + AttributeDecoder<Output = F>>() {
+        use std::str::FromStr;
+
+        let res = F::encode_from_decimal_str("-85000.25", 2).unwrap();
+        assert_eq!(F::zero_center() - F::from(8_500_025u64), res);
+        assert_eq!("-85000.25", F::decode_to_decimal(&res, 2).unwrap().to_string());
+
+        for value in ["1e72", "-1e72"].iter() {
+            let value = bigdecimal::BigDecimal::from_str(value).unwrap();
+            let res = F::encode_from_decimal(&value, 2).unwrap();
+            assert_eq!(value, F::decode_to_decimal(&res, 2).unwrap());
+        }
+        let res = F::encode_from_decimal_str("-1e74", 2);
+        assert_eq!(ErrorKind::OutOfRange, res.err().unwrap().kind());
+    }
+
+    #[test]
+    fn fixed_point_test() {
+        for_each_field!(fixed_point());
+    }
+
+    fn decode<F: PrimeField + AttributeDecoder<Output = F>>() {
+        assert_eq!(ErrorKind::OutOfRange, F::from_vec(vec![1u8; 33]).err().unwrap().kind());
+        assert_eq!(usize::MAX, F::decode_to_usize(&F::encode_from_usize(usize::MAX).unwrap()).unwrap());
+        assert!(F::decode_to_usize(&F::encode_from_isize(-1isize).unwrap()).is_err());
+        assert!(F::is_encoded_null(&F::encoded_null().unwrap()).unwrap());
+        assert!(!F::is_encoded_null(&F::zero_center()).unwrap());
+    }
+
+    #[test]
+    fn decode_test() {
+        for_each_field!(decode());
+    }
+}
@@ -0,0 +1,6 @@
+use super::{AttributeDecoder, AttributeEncoder};
+use crate::errors::{Error, ErrorKind};
+
+ark_field_encoder!(ark_bls12_377::Fr,
+    /// r is just above 2^252 so there is slightly more room above `zero_center()` than below it
+    MAX_POSITIVE_BITS = 251);
@@ -1,32 +1,40 @@
-use super::{AttributeEncoder, BITS_IN_ZERO};
+use super::{AttributeDecoder, AttributeEncoder};
+use crate::errors::{Error, ErrorKind};
 
 use amcl_wrapper::field_elem::FieldElement;
 
 impl AttributeEncoder for FieldElement {
     type Output = FieldElement;
 
+    const BITS_IN_ZERO: usize = 254;
+
     fn max() -> Self::Output {
         let co: amcl_wrapper::types::BigNum = *amcl_wrapper::constants::CurveOrder;
         FieldElement::from(co)
     }
 
     fn zero_center() -> Self::Output {
-        FieldElement::one().shift_left(BITS_IN_ZERO)
+        FieldElement::one().shift_left(Self::BITS_IN_ZERO)
     }
 
-    fn from_vec(bytes: Vec<u8>) -> Self::Output {
+    fn from_vec(bytes: Vec<u8>) -> Result<Self::Output, Error> {
         let mut data;
         if bytes.len() < amcl_wrapper::constants::FieldElement_SIZE {
             data = vec![0u8; amcl_wrapper::constants::FieldElement_SIZE - bytes.len()];
             data.extend_from_slice(&bytes); 
         } else if bytes.len() > amcl_wrapper::constants::FieldElement_SIZE {
-            data = vec![0u8; amcl_wrapper::constants::FieldElement_SIZE];
-            data.copy_from_slice(&bytes[..amcl_wrapper::constants::FieldElement_SIZE]);
+            return Err(Error::new(ErrorKind::OutOfRange, format!("Expected at most {} bytes, found {}", amcl_wrapper::constants::FieldElement_SIZE, bytes.len())));
         } else {
             data = vec![0u8; amcl_wrapper::constants::FieldElement_SIZE];
             data.copy_from_slice(bytes.as_slice());
         }
-        FieldElement::from_bytes(data.as_slice()).map_err(|e| format!("{:?}", e)).unwrap()
+        FieldElement::from_bytes(data.as_slice()).map_err(|e| Error::new(ErrorKind::Backend, format!("{:?}", e)))
+    }
+}
+
+impl AttributeDecoder for FieldElement {
+    fn to_vec(value: &Self::Output) -> Vec<u8> {
+        value.to_bytes()
     }
 }
 
@@ -51,68 +59,149 @@ mod tests {
 
         let res = FieldElement::encode_from_rfc3339_as_unixtimestamp("1900");
         assert!(res.is_err());
+        assert_eq!(ErrorKind::InvalidDate, res.unwrap_err().kind());
 
         let res = FieldElement::encode_from_rfc3339_as_dayssince1900("1982-12-20T10:45:00.000-06:00");
         assert!(res.is_ok());
         assert_eq!(res.unwrap(), FieldElement::zero_center() + FieldElement::from(30303));
     }
 
+    #[test]
+    fn bool_enum_test() {
+        let res = FieldElement::encode_from_bool(false);
+        assert!(res.is_ok());
+        assert_eq!(FieldElement::zero_center(), res.unwrap());
+        let res = FieldElement::encode_from_bool(true);
+        assert!(res.is_ok());
+        let res = res.unwrap();
+        assert_eq!(FieldElement::zero_center() + FieldElement::from(1u64), res);
+        assert!(FieldElement::decode_to_bool(&res).unwrap());
+        assert!(FieldElement::decode_to_bool(&(FieldElement::zero_center() + FieldElement::from(2u64))).is_err());
+
+        let variants = ["passport", "drivers_license", "national_id"];
+        let res = FieldElement::encode_from_enum(&"national_id", &variants);
+        assert!(res.is_ok());
+        let res = res.unwrap();
+        assert_eq!(FieldElement::zero_center() + FieldElement::from(2u64), res);
+        assert_eq!(&"national_id", FieldElement::decode_to_enum(&res, &variants).unwrap());
+
+        let res = FieldElement::encode_from_enum(&"birth_certificate", &variants);
+        assert_eq!(ErrorKind::InvalidInput, res.unwrap_err().kind());
+        let res = FieldElement::encode_from_enum(&"passport", &["passport", "passport"]);
+        assert_eq!(ErrorKind::InvalidInput, res.unwrap_err().kind());
+        let res = FieldElement::decode_to_enum(&(FieldElement::zero_center() + FieldElement::from(3u64)), &variants);
+        assert_eq!(ErrorKind::OutOfRange, res.unwrap_err().kind());
+    }
+
     #[test]
     fn decimal_test() {
         let res1 = FieldElement::encode_from_f64(1.33f32);
         assert!(res1.is_ok());
         let res2 = FieldElement::encode_from_f64(-1.33f32);
         assert!(res2.is_ok());
-        assert_eq!(FieldElement::zero_center(), res1.unwrap() + res2.unwrap());
+        assert_eq!(FieldElement::zero_center() + FieldElement::zero_center(), res1.unwrap() + res2.unwrap());
 
-        let res1 = FieldElement::encode_from_f64(std::f64::MAX);
+        let res1 = FieldElement::encode_from_f64(f64::MAX);
         assert!(res1.is_ok());
         let res2 = res1.unwrap();
         assert_eq!((&res2 - &res2), FieldElement::new());
 
-        let res3 = FieldElement::encode_from_f64(std::f64::MIN);
+        let res3 = FieldElement::encode_from_f64(f64::MIN);
         assert!(res3.is_ok());
-        assert_eq!(FieldElement::zero_center(), &res3.unwrap() + &res2);
+        assert_eq!(FieldElement::zero_center() + FieldElement::zero_center(), &res3.unwrap() + &res2);
 
-        let res1 = FieldElement::encode_from_f64(std::f64::NEG_INFINITY);
+        let res1 = FieldElement::encode_from_f64(f64::NEG_INFINITY);
         assert!(res1.is_ok());
         assert_eq!(FieldElement::from(8), res1.unwrap());
 
         let pos_inf = <FieldElement as AttributeEncoder>::max() - FieldElement::from(9u64);
-        let res1 = FieldElement::encode_from_f64(std::f64::INFINITY);
+        let res1 = FieldElement::encode_from_f64(f64::INFINITY);
         assert!(res1.is_ok());
         assert_eq!(pos_inf, res1.unwrap());
 
-        let res1 = FieldElement::encode_from_f64(std::f64::NAN);
+        let res1 = FieldElement::encode_from_f64(f64::NAN);
         assert!(res1.is_ok());
         let res2 = <FieldElement as AttributeEncoder>::max() - FieldElement::from(8u64);
         assert_eq!(res2, res1.unwrap());
     }
 
     #[test]
-    fn size_test() {
+    fn fixed_point_test() {
+        use std::str::FromStr;
+
+        let res = FieldElement::encode_from_decimal_str("85000.25", 2);
+        assert!(res.is_ok());
+        let res = res.unwrap();
+        assert_eq!(FieldElement::zero_center() + FieldElement::from(8_500_025u64), res);
+        assert_eq!(bigdecimal::BigDecimal::from_str("85000.25").unwrap(), FieldElement::decode_to_decimal(&res, 2).unwrap());
+
+        let res = FieldElement::encode_from_decimal_str("-0.10", 2);
+        assert!(res.is_ok());
+        let res = res.unwrap();
+        assert_eq!(FieldElement::zero_center() - FieldElement::from(10u64), res);
+        assert_eq!(bigdecimal::BigDecimal::from_str("-0.1").unwrap(), FieldElement::decode_to_decimal(&res, 2).unwrap());
+
+        let res = FieldElement::encode_from_decimal(&bigdecimal::BigDecimal::from_str("1.500").unwrap(), 1);
+        assert!(res.is_ok());
+        assert_eq!(FieldElement::zero_center() + FieldElement::from(15u64), res.unwrap());
+
+        let res = FieldElement::encode_from_decimal_str("1.005", 2);
+        assert_eq!(ErrorKind::InvalidInput, res.unwrap_err().kind());
+        let res = FieldElement::encode_from_decimal_str(format!("1.{}", "0".repeat(300)).as_str(), 2);
+        assert_eq!(FieldElement::zero_center() + FieldElement::from(100u64), res.unwrap());
+        let res = FieldElement::encode_from_decimal_str(format!("1.{}1", "0".repeat(300)).as_str(), 2);
+        assert_eq!(ErrorKind::InvalidInput, res.unwrap_err().kind());
+        let res = FieldElement::encode_from_decimal_str("salary", 2);
+        assert_eq!(ErrorKind::InvalidInput, res.unwrap_err().kind());
+        let res = FieldElement::encode_from_decimal_str("1e75", 2);
+        assert_eq!(ErrorKind::OutOfRange, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn decode_test() {
         let mut test_vectors = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         test_vectors.push("test_vectors");
         test_vectors.push("integers.txt");
-        let lines = std::fs::read_to_string(test_vectors).unwrap().split("\n").map(|s| s.to_string()).collect::<Vec<String>>();
-        assert_eq!(lines.len(), 7);
-        for i in 0..lines.len() - 2 {
-            let parts = lines[i].split(",").collect::<Vec<&str>>();
-            let value = parts[0].parse::<isize>().unwrap();
-            let expected = FieldElement::from_hex(parts[1].to_string()).unwrap();
-            let res = FieldElement::encode_from_isize(value);
-            assert!(res.is_ok());
-            assert_eq!(expected, res.unwrap());
+        let lines = std::fs::read_to_string(test_vectors).unwrap().split('\n').map(|s| s.to_string()).collect::<Vec<String>>();
+        for line in &lines[..lines.len() - 2] {
+            let parts = line.split(',').collect::<Vec<&str>>();
+            let value = parts[0].parse::<i64>().unwrap();
+            let res = FieldElement::decode_to_i64(&FieldElement::encode_from_i64(value).unwrap());
+            assert_eq!(value, res.unwrap());
         }
-        let parts = lines[lines.len() - 2].split(",").collect::<Vec<&str>>();
-        let value = parts[0].parse::<usize>().unwrap();
-        let expected = FieldElement::from_hex(parts[1].to_string()).unwrap();
-        let res = FieldElement::encode_from_usize(value);
-        assert!(res.is_ok());
-        assert_eq!(expected, res.unwrap());
+        let parts = lines[lines.len() - 2].split(',').collect::<Vec<&str>>();
+        let value = parts[0].parse::<u64>().unwrap();
+        let res = FieldElement::decode_to_u64(&FieldElement::encode_from_u64(value).unwrap());
+        assert_eq!(value, res.unwrap());
+        assert!(FieldElement::decode_to_usize(&FieldElement::encode_from_isize(-1isize).unwrap()).is_err());
+
+        assert_eq!(ErrorKind::OutOfRange, FieldElement::from_vec(vec![1u8; 49]).unwrap_err().kind());
+        assert_eq!(ErrorKind::OutOfRange, FieldElement::decode_to_isize(&FieldElement::encoded_null().unwrap()).unwrap_err().kind());
+        assert!(FieldElement::is_encoded_null(&FieldElement::encoded_null().unwrap()).unwrap());
+        assert!(!FieldElement::is_encoded_null(&FieldElement::zero_center()).unwrap());
+
+        let mut test_vectors = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_vectors.push("test_vectors");
+        test_vectors.push("floating.txt");
+        let contents = std::fs::read_to_string(test_vectors).unwrap();
+        for line in contents.lines() {
+            let parts = line.split(',').collect::<Vec<&str>>();
+            let value = match parts[0].trim() {
+                "subnormal" => f64::MIN_POSITIVE / 2.0,
+                v => v.parse::<f64>().unwrap()
+            };
+            let res = FieldElement::decode_to_f64(&FieldElement::encode_from_f64(value).unwrap()).unwrap();
+            if value.is_nan() {
+                assert!(res.is_nan());
+            } else {
+                assert_eq!(value, res);
+            }
+        }
+
+        let res = FieldElement::encode_from_rfc3339_as_unixtimestamp("2018-01-26T18:30:09.000+00:00").unwrap();
+        assert_eq!("2018-01-26T18:30:09+00:00", FieldElement::decode_unixtimestamp_to_datetime(&res).unwrap().to_rfc3339());
 
-        let parts = lines[lines.len() - 1].split(",").collect::<Vec<&str>>();
-        assert_eq!(parts[0], "null");
-        assert_eq!(FieldElement::from_hex(parts[1].to_string()).unwrap(), FieldElement::encoded_null().unwrap());
+        let res = FieldElement::encode_from_rfc3339_as_dayssince1900("1982-12-20T10:45:00.000-06:00").unwrap();
+        assert_eq!(chrono::NaiveDate::from_ymd_opt(1982, 12, 20).unwrap(), FieldElement::decode_dayssince1900_to_date(&res).unwrap());
     }
 }
\ No newline at end of file
@@ -0,0 +1,6 @@
+use super::{AttributeDecoder, AttributeEncoder};
+use crate::errors::{Error, ErrorKind};
+
+ark_field_encoder!(ark_bn254::Fr,
+    /// r is just below 2^254 so there is more room above `zero_center()` than below it
+    MAX_POSITIVE_BITS = 253);
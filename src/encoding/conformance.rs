@@ -0,0 +1,193 @@
+//! Data driven conformance tests that run every file in `test_vectors`
+//! through every enabled backend. Files in `test_vectors/<backend>`
+//! only run against that backend and replace the shared file of the same name.
+//!
+//! Each line is `input,expected` where `expected` is either the hex encoded
+//! integer or `max-N` for values relative to `AttributeEncoder::max()`.
+//! Expected integers are passed through `from_vec` so backends that reduce
+//! modulo a group order compare against the reduced value.
+use super::{hash_to_field, AttributeDecoder, MIN_OFFSET_BITS};
+use crate::errors::{Error, ErrorKind};
+
+use bigdecimal::BigDecimal;
+use num_bigint::{BigInt, BigUint};
+use std::path::PathBuf;
+
+/// The encoding used for each vector file
+fn encode<E: AttributeDecoder>(file: &str, input: &str) -> Result<E::Output, Error> {
+    match file {
+        "integers.txt" => {
+            if input == "null" {
+                E::encoded_null()
+            } else if let Ok(value) = input.parse::<i64>() {
+                E::encode_from_i64(value)
+            } else {
+                E::encode_from_u64(input.parse::<u64>().unwrap())
+            }
+        },
+        "floating.txt" => {
+            let value = match input {
+                "subnormal" => f64::MIN_POSITIVE / 2.0,
+                v => v.parse::<f64>().unwrap()
+            };
+            E::encode_from_f64(value)
+        },
+        "fixedwidth.txt" => {
+            let (width, value) = input.split_at(input.find(':').unwrap());
+            let value = &value[1..];
+            match width {
+                "i8" => E::encode_from_i8(value.parse().unwrap()),
+                "i16" => E::encode_from_i16(value.parse().unwrap()),
+                "i32" => E::encode_from_i32(value.parse().unwrap()),
+                "i64" => E::encode_from_i64(value.parse().unwrap()),
+                "u8" => E::encode_from_u8(value.parse().unwrap()),
+                "u16" => E::encode_from_u16(value.parse().unwrap()),
+                "u32" => E::encode_from_u32(value.parse().unwrap()),
+                "u64" => E::encode_from_u64(value.parse().unwrap()),
+                _ => panic!("Unknown integer width {}", width)
+            }
+        },
+        "unixtimestamps.txt" => E::encode_from_rfc3339_as_unixtimestamp(input),
+        "dayssince1900.txt" => E::encode_from_rfc3339_as_dayssince1900(input),
+        "unixtimestampmillis.txt" => E::encode_from_rfc3339_as_unixtimestamp_millis(input),
+        "dates.txt" => E::encode_from_iso8601_date_as_dayssince1900(input),
+        "times.txt" => E::encode_from_iso8601_time_as_milliseconds(input),
+        "durations.txt" => E::encode_from_iso8601_duration_as_milliseconds(input),
+        "sha256.txt" => E::encode_from_utf8_as_hash::<_, sha2::Sha256>(input),
+        _ => panic!("No encoding registered for test vector file {}", file)
+    }
+}
+
+fn expected<E: AttributeDecoder>(value: &str) -> E::Output {
+    if let Some(offset) = value.strip_prefix("max-") {
+        E::max() - E::Output::from(offset.parse::<u64>().unwrap())
+    } else {
+        let n = BigUint::parse_bytes(value.as_bytes(), 16).unwrap();
+        E::from_vec(n.to_bytes_be()).unwrap()
+    }
+}
+
+fn to_biguint<E: AttributeDecoder>(value: &E::Output) -> BigUint {
+    BigUint::from_bytes_be(E::to_vec(value).as_slice())
+}
+
+/// List the vector files for `backend`. A file in `test_vectors/<backend>`
+/// replaces the shared file with the same name
+fn vector_files(backend: &str) -> Vec<PathBuf> {
+    let mut shared = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    shared.push("test_vectors");
+    let specific = shared.join(backend);
+    let mut files = Vec::new();
+    if specific.is_dir() {
+        files.extend(std::fs::read_dir(&specific).unwrap().map(|e| e.unwrap().path()));
+    }
+    for entry in std::fs::read_dir(&shared).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_file() && !specific.join(path.file_name().unwrap()).is_file() {
+            files.push(path);
+        }
+    }
+    files
+}
+
+/// Run every vector through `E` and return a description of each mismatch
+/// along with any problems with its range
+fn check<E: AttributeDecoder>(backend: &str) -> Vec<String> {
+    let mut failures = Vec::new();
+    for path in vector_files(backend) {
+        let file = path.file_name().unwrap().to_str().unwrap().to_string();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        for (i, line) in contents.lines().enumerate() {
+            let mut parts = line.rsplitn(2, ',');
+            let value = parts.next().unwrap().trim();
+            let input = parts.next().unwrap().trim();
+            let expected = to_biguint::<E>(&expected::<E>(value));
+            match encode::<E>(&file, input) {
+                Ok(actual) => {
+                    let actual = to_biguint::<E>(&actual);
+                    if actual != expected {
+                        failures.push(format!("{} {}:{} `{}`: expected {:X}, found {:X}", backend, path.display(), i + 1, input, expected, actual));
+                    }
+                },
+                Err(e) => failures.push(format!("{} {}:{} `{}`: {}", backend, path.display(), i + 1, input, e))
+            }
+        }
+    }
+    failures.extend(check_range::<E>(backend));
+    failures
+}
+
+/// Check that `MAX_POSITIVE_BITS` and `MAX_NEGATIVE_BITS` keep every
+/// encoded value strictly between the -infinity and +infinity sentinels
+fn check_range<E: AttributeDecoder>(backend: &str) -> Vec<String> {
+    let mut failures = Vec::new();
+    if E::MAX_POSITIVE_BITS < MIN_OFFSET_BITS || E::MAX_NEGATIVE_BITS < MIN_OFFSET_BITS {
+        failures.push(format!("{}: offsets must allow at least {} bits", backend, MIN_OFFSET_BITS));
+    }
+    let zero = to_biguint::<E>(&E::zero_center());
+    let top = to_biguint::<E>(&expected::<E>("max-9"));
+    if &zero + (BigUint::from(1u8) << E::MAX_POSITIVE_BITS) > top {
+        failures.push(format!("{}: {} positive bits reach +infinity", backend, E::MAX_POSITIVE_BITS));
+    }
+    if zero <= (BigUint::from(1u8) << E::MAX_NEGATIVE_BITS) + BigUint::from(8u8) {
+        failures.push(format!("{}: {} negative bits reach -infinity", backend, E::MAX_NEGATIVE_BITS));
+    }
+
+    for (bits, sign) in [(E::MAX_POSITIVE_BITS, 1), (E::MAX_NEGATIVE_BITS, -1)].iter() {
+        let largest = BigDecimal::new((BigInt::from(1) << *bits) - 1, 0) * BigDecimal::from(*sign);
+        match E::encode_from_decimal(&largest, 0).and_then(|v| E::decode_to_decimal(&v, 0)) {
+            Ok(v) if v == largest => {},
+            Ok(v) => failures.push(format!("{}: {} decoded as {}", backend, largest, v)),
+            Err(e) => failures.push(format!("{}: {}", backend, e)),
+        }
+        let too_large = BigDecimal::new(BigInt::from(1) << *bits, 0) * BigDecimal::from(*sign);
+        match E::encode_from_decimal(&too_large, 0) {
+            Err(ref e) if e.kind() == ErrorKind::OutOfRange => {},
+            _ => failures.push(format!("{}: {} should be out of range", backend, too_large)),
+        }
+        let largest = ((BigInt::from(1) << *bits) - 1) * *sign;
+        match E::encode_from_bigint(&largest).and_then(|v| E::decode_to_bigint(&v)) {
+            Ok(v) if v == largest => {},
+            Ok(v) => failures.push(format!("{}: {} decoded as {}", backend, largest, v)),
+            Err(e) => failures.push(format!("{}: {}", backend, e)),
+        }
+        match E::encode_from_bigint(&((BigInt::from(1) << *bits) * *sign)) {
+            Err(ref e) if e.kind() == ErrorKind::OutOfRange => {},
+            _ => failures.push(format!("{}: 2^{} should be out of range", backend, bits)),
+        }
+    }
+
+    if to_biguint::<E>(&expected::<E>("max-9")) + BigUint::from(9u8) != hash_to_field::top::<E>().unwrap().to_biguint().unwrap() {
+        failures.push(format!("{}: hash to field range does not end at +infinity", backend));
+    }
+    for value in ["", "Alice"].iter() {
+        match hash_to_field::encode_from_utf8_to_field::<E, sha2::Sha256>(value, "conformance") {
+            Ok(v) if to_biguint::<E>(&v) > BigUint::from(8u8) && to_biguint::<E>(&v) < top => {},
+            Ok(_) => failures.push(format!("{}: hash to field of '{}' is a sentinel", backend, value)),
+            Err(e) => failures.push(format!("{}: {}", backend, e)),
+        }
+    }
+    failures
+}
+
+#[test]
+fn conformance_test() {
+    let mut failures = Vec::new();
+    #[cfg(feature = "bls377")]
+    failures.extend(check::<ark_bls12_377::Fr>("bls377"));
+    #[cfg(feature = "bls381")]
+    failures.extend(check::<amcl_wrapper::field_elem::FieldElement>("bls381"));
+    #[cfg(feature = "bn254")]
+    failures.extend(check::<ark_bn254::Fr>("bn254"));
+    #[cfg(feature = "ristretto255")]
+    failures.extend(check::<curve25519_dalek::scalar::Scalar>("ristretto255"));
+    #[cfg(feature = "rsa-native")]
+    failures.extend(check::<super::rsa_native::BigNumber>("rsa-native"));
+    #[cfg(feature = "rsa-pure")]
+    failures.extend(check::<super::rsa_pure::BigInteger>("rsa-pure"));
+    #[cfg(feature = "secp256k1")]
+    failures.extend(check::<k256::Scalar>("secp256k1"));
+    #[cfg(feature = "secp256r1")]
+    failures.extend(check::<p256::Scalar>("secp256r1"));
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
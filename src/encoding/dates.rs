@@ -0,0 +1,215 @@
+use super::{days_since_1900, encode_offset, epoch_1900, AttributeEncoder};
+use crate::errors::{Error, ErrorKind};
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+/// The unit of dates given as a number since the unix epoch
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum EpochUnit {
+    /// Seconds since 1970-01-01T00:00:00Z
+    Seconds,
+    /// Milliseconds since 1970-01-01T00:00:00Z
+    Milliseconds,
+}
+
+/// Parses dates that are not formatted according to RFC3339 so they can be
+/// encoded the same as `encode_from_rfc3339_as_unixtimestamp`,
+/// `encode_from_rfc3339_as_unixtimestamp_millis` and `encode_from_rfc3339_as_dayssince1900`.
+///
+/// RFC3339 is always accepted. Each `chrono` format is tried in the order added,
+/// and formats without an offset use the assumed timezone.
+/// Numeric epochs are only tried after every format so a numeric format
+/// such as "%Y%m%d" is not read as an epoch.
+/// Dates without a time are midnight in the assumed timezone, except that
+/// `encode_as_dayssince1900` encodes their calendar date unchanged like
+/// `encode_from_iso8601_date_as_dayssince1900`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DateParser {
+    formats: Vec<String>,
+    timezone: FixedOffset,
+    epoch: Option<EpochUnit>,
+}
+
+impl DateParser {
+    /// Create a parser that only accepts RFC3339 and assumes UTC
+    pub fn new() -> Self {
+        Self {
+            formats: Vec::new(),
+            timezone: FixedOffset::east_opt(0).expect("UTC is a valid offset"),
+            epoch: None,
+        }
+    }
+
+    /// Also accept dates in the `chrono` `format`, i.e. "%d/%m/%Y" or "%b %d %Y"
+    pub fn with_format<A: Into<String>>(mut self, format: A) -> Self {
+        self.formats.push(format.into());
+        self
+    }
+
+    /// The timezone for dates that do not include an offset
+    pub fn with_timezone(mut self, timezone: FixedOffset) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// Also accept integers as a count of `unit` since the unix epoch
+    pub fn with_epoch(mut self, unit: EpochUnit) -> Self {
+        self.epoch = Some(unit);
+        self
+    }
+
+    /// Parse `value` with the first accepted format that matches
+    pub fn parse(&self, value: &str) -> Result<DateTime<FixedOffset>, Error> {
+        match self.parse_value(value)? {
+            Parsed::DateTime(dt) => Ok(dt),
+            Parsed::Date(date) => self.localize(value, &date.and_hms_opt(0, 0, 0).expect("midnight is a valid time")),
+        }
+    }
+
+    fn parse_value(&self, value: &str) -> Result<Parsed, Error> {
+        if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+            return Ok(Parsed::DateTime(dt));
+        }
+        for format in &self.formats {
+            if let Ok(dt) = DateTime::parse_from_str(value, format) {
+                return Ok(Parsed::DateTime(dt));
+            }
+            if let Ok(naive) = NaiveDateTime::parse_from_str(value, format) {
+                return self.localize(value, &naive).map(Parsed::DateTime);
+            }
+            if let Ok(date) = NaiveDate::parse_from_str(value, format) {
+                return Ok(Parsed::Date(date));
+            }
+        }
+        if let Some(unit) = self.epoch {
+            if let Ok(n) = value.parse::<i64>() {
+                let dt = match unit {
+                    EpochUnit::Seconds => Utc.timestamp_opt(n, 0).single(),
+                    EpochUnit::Milliseconds => Utc.timestamp_millis_opt(n).single(),
+                };
+                return dt
+                    .map(|dt| Parsed::DateTime(dt.with_timezone(&self.timezone)))
+                    .ok_or_else(|| Error::new(ErrorKind::OutOfRange, format!("{} is out of range for {:?} since the unix epoch", n, unit)));
+            }
+        }
+        Err(Error::new(ErrorKind::InvalidDate, format!("'{}' does not match any accepted date format", value)))
+    }
+
+    fn localize(&self, value: &str, naive: &NaiveDateTime) -> Result<DateTime<FixedOffset>, Error> {
+        self.timezone
+            .from_local_datetime(naive)
+            .single()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidDate, format!("'{}' does not exist in {}", value, self.timezone)))
+    }
+
+    /// Parse `value` and encode it as seconds since the unix epoch.
+    /// See `AttributeEncoder::encode_from_rfc3339_as_unixtimestamp`
+    pub fn encode_as_unixtimestamp<E: AttributeEncoder + ?Sized>(&self, value: &str) -> Result<E::Output, Error> {
+        encode_offset::<E>(self.parse(value)?.timestamp())
+    }
+
+    /// Parse `value` and encode it as milliseconds since the unix epoch.
+    /// See `AttributeEncoder::encode_from_rfc3339_as_unixtimestamp_millis`
+    pub fn encode_as_unixtimestamp_millis<E: AttributeEncoder + ?Sized>(&self, value: &str) -> Result<E::Output, Error> {
+        encode_offset::<E>(self.parse(value)?.timestamp_millis())
+    }
+
+    /// Parse `value` and encode it as days since 1900-01-01.
+    /// Dates without a time keep their calendar date.
+    /// See `AttributeEncoder::encode_from_rfc3339_as_dayssince1900`
+    pub fn encode_as_dayssince1900<E: AttributeEncoder + ?Sized>(&self, value: &str) -> Result<E::Output, Error> {
+        let days = match self.parse_value(value)? {
+            Parsed::Date(date) => date.signed_duration_since(epoch_1900()).num_days(),
+            Parsed::DateTime(dt) => days_since_1900(&dt),
+        };
+        encode_offset::<E>(days)
+    }
+}
+
+/// A parsed value, keeping dates without a time apart so the assumed
+/// timezone does not move them to another day
+enum Parsed {
+    Date(NaiveDate),
+    DateTime(DateTime<FixedOffset>),
+}
+
+impl Default for DateParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(all(test, feature = "rsa-pure"))]
+mod tests {
+    use super::*;
+    use crate::encoding::rsa_pure::BigInteger;
+
+    fn parser() -> DateParser {
+        DateParser::new()
+            .with_format("%Y-%m-%d")
+            .with_format("%d/%m/%Y")
+            .with_format("%b %d %Y")
+            .with_format("%Y-%m-%d %H:%M:%S %z")
+            .with_epoch(EpochUnit::Seconds)
+    }
+
+    #[test]
+    fn parse_test() {
+        let expected = BigInteger::encode_from_rfc3339_as_dayssince1900("1982-12-20T00:00:00Z").unwrap();
+        for value in ["1982-12-20", "20/12/1982", "Dec 20 1982", "1982-12-20T10:45:00.000-06:00", "409233600"].iter() {
+            assert_eq!(expected, parser().encode_as_dayssince1900::<BigInteger>(value).unwrap(), "{}", value);
+        }
+
+        let expected = BigInteger::encode_from_rfc3339_as_unixtimestamp("2018-01-26T18:30:09+01:00").unwrap();
+        assert_eq!(expected, parser().encode_as_unixtimestamp::<BigInteger>("2018-01-26 18:30:09 +0100").unwrap());
+        assert_eq!(expected, parser().encode_as_unixtimestamp::<BigInteger>("1516987809").unwrap());
+        let millis = DateParser::new().with_epoch(EpochUnit::Milliseconds);
+        assert_eq!(
+            BigInteger::encode_from_rfc3339_as_unixtimestamp_millis("1969-12-31T23:59:59.999Z").unwrap(),
+            millis.encode_as_unixtimestamp_millis::<BigInteger>("-1").unwrap()
+        );
+    }
+
+    #[test]
+    fn timezone_test() {
+        let sydney = FixedOffset::east_opt(10 * 3600).unwrap();
+        let parser = parser().with_timezone(sydney);
+        assert_eq!(
+            BigInteger::encode_from_rfc3339_as_unixtimestamp("1982-12-20T00:00:00+10:00").unwrap(),
+            parser.encode_as_unixtimestamp::<BigInteger>("20/12/1982").unwrap()
+        );
+        // The calendar date is kept even though midnight in Sydney is the previous day in UTC
+        assert_eq!(
+            BigInteger::encode_from_iso8601_date_as_dayssince1900("1982-12-20").unwrap(),
+            parser.encode_as_dayssince1900::<BigInteger>("20/12/1982").unwrap()
+        );
+        // Inputs with a time are still converted to UTC
+        assert_eq!(
+            BigInteger::encode_from_rfc3339_as_dayssince1900("1982-12-19T00:00:00Z").unwrap(),
+            parser.with_format("%d/%m/%Y %H:%M").encode_as_dayssince1900::<BigInteger>("20/12/1982 09:00").unwrap()
+        );
+    }
+
+    #[test]
+    fn numeric_format_test() {
+        let parser = DateParser::new().with_format("%Y%m%d").with_epoch(EpochUnit::Seconds);
+        assert_eq!(
+            BigInteger::encode_from_iso8601_date_as_dayssince1900("1982-12-20").unwrap(),
+            parser.encode_as_dayssince1900::<BigInteger>("19821220").unwrap()
+        );
+        assert_eq!(
+            BigInteger::encode_from_rfc3339_as_unixtimestamp("1982-12-20T12:00:00Z").unwrap(),
+            parser.encode_as_unixtimestamp::<BigInteger>("409233600").unwrap()
+        );
+    }
+
+    #[test]
+    fn reject_test() {
+        let err = DateParser::new().parse("1982-12-20").unwrap_err();
+        assert_eq!(ErrorKind::InvalidDate, err.kind());
+        assert_eq!("'1982-12-20' does not match any accepted date format", err.message());
+        assert_eq!(ErrorKind::InvalidDate, parser().parse("31/02/1982").unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidDate, DateParser::new().parse("409233600").unwrap_err().kind());
+        assert_eq!(ErrorKind::OutOfRange, parser().parse(i64::MAX.to_string().as_str()).unwrap_err().kind());
+    }
+}
@@ -0,0 +1,124 @@
+/// The number of bytes in a serialized elliptic curve scalar
+pub(super) const SCALAR_SIZE: usize = 32;
+
+/// Implements `AttributeEncoder` and `AttributeDecoder` for the `Scalar`
+/// of a RustCrypto elliptic curve crate whose group order is just below 2^256.
+/// `$curve` is the crate, e.g. `k256`
+macro_rules! elliptic_curve_encoder {
+    ($curve:ident) => {
+        use $curve::{elliptic_curve::ops::Reduce, Scalar, U256};
+
+        impl AttributeEncoder for Scalar {
+            type Output = Scalar;
+
+            const BITS_IN_ZERO: usize = 254;
+            /// The group order is just below 2^256 so positive values have more room than negative ones
+            const MAX_POSITIVE_BITS: usize = 255;
+
+            fn max() -> Self::Output {
+                Scalar::ZERO
+            }
+
+            fn zero_center() -> Self::Output {
+                <Scalar as Reduce<U256>>::reduce(U256::ONE.shl_vartime(Self::BITS_IN_ZERO))
+            }
+
+            fn from_vec(bytes: Vec<u8>) -> Result<Self::Output, Error> {
+                if bytes.len() > $crate::encoding::elliptic_curve_scalar::SCALAR_SIZE {
+                    return Err(Error::new(ErrorKind::OutOfRange, format!("Expected at most {} bytes, found {}", $crate::encoding::elliptic_curve_scalar::SCALAR_SIZE, bytes.len())));
+                }
+                let mut data = vec![0u8; $crate::encoding::elliptic_curve_scalar::SCALAR_SIZE - bytes.len()];
+                data.extend_from_slice(bytes.as_slice());
+                Ok(<Scalar as Reduce<U256>>::reduce(U256::from_be_slice(data.as_slice())))
+            }
+        }
+
+        impl AttributeDecoder for Scalar {
+            fn to_vec(value: &Self::Output) -> Vec<u8> {
+                value.to_bytes().to_vec()
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::encoding::AttributeDecoder;
+    use crate::errors::ErrorKind;
+    use std::fmt::Debug;
+
+    /// Call `$check::<S>($args)` for every enabled elliptic curve scalar `S`
+    macro_rules! for_each_scalar {
+        ($check:ident($($arg:expr),*)) => {{
+            #[cfg(feature = "secp256k1")]
+            $check::<k256::Scalar>($($arg),*);
+            #[cfg(feature = "secp256r1")]
+            $check::<p256::Scalar>($($arg),*);
+        }};
+    }
+
+    fn rfc3339_string_convert<S: AttributeDecoder>() where S::Output: Debug + PartialEq {
+        let res = S::encode_from_rfc3339_as_unixtimestamp("2018-01-26T18:30:09.453+00:00");
+        assert_eq!(S::Output::from(1_516_991_409u64) + S::zero_center(), res.unwrap());
+
+        let res = S::encode_from_rfc3339_as_unixtimestamp("1900");
+        assert_eq!(ErrorKind::InvalidDate, res.unwrap_err().kind());
+
+        let res = S::encode_from_rfc3339_as_dayssince1900("1982-12-20T10:45:00.000-06:00").unwrap();
+        assert_eq!(res, S::zero_center() + S::Output::from(30303u64));
+        assert_eq!(chrono::NaiveDate::from_ymd_opt(1982, 12, 20).unwrap(), S::decode_dayssince1900_to_date(&res).unwrap());
+    }
+
+    #[test]
+    fn rfc3339_string_convert_test() {
+        for_each_scalar!(rfc3339_string_convert());
+    }
+
+    fn decimal<S: AttributeDecoder>() where S::Output: Debug + PartialEq {
+        let res1 = S::encode_from_f64(1.33f32).unwrap();
+        let res2 = S::encode_from_f64(-1.33f32).unwrap();
+        assert_eq!(S::zero_center() + S::zero_center(), res1 + res2);
+
+        assert_eq!(S::Output::from(8u64), S::encode_from_f64(f64::NEG_INFINITY).unwrap());
+        assert_eq!(-S::Output::from(9u64), S::encode_from_f64(f64::INFINITY).unwrap());
+        assert_eq!(-S::Output::from(8u64), S::encode_from_f64(f64::NAN).unwrap());
+    }
+
+    #[test]
+    fn decimal_test() {
+        for_each_scalar!(decimal());
+    }
+
+    fn fixed_point<S: AttributeDecoder>() where S::Output: Debug + PartialEq {
+        use std::str::FromStr;
+
+        let res = S::encode_from_decimal_str("-85000.25", 2).unwrap();
+        assert_eq!(S::zero_center() - S::Output::from(8_500_025u64), res);
+        assert_eq!("-85000.25", S::decode_to_decimal(&res, 2).unwrap().to_string());
+
+        let value = bigdecimal::BigDecimal::from_str("-1e74").unwrap();
+        let res = S::encode_from_decimal(&value, 2).unwrap();
+        assert_eq!(value, S::decode_to_decimal(&res, 2).unwrap());
+        let res = S::encode_from_decimal_str("1e75", 2);
+        assert_eq!(ErrorKind::OutOfRange, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn fixed_point_test() {
+        for_each_scalar!(fixed_point());
+    }
+
+    fn decode<S: AttributeDecoder>() where S::Output: Debug + PartialEq {
+        assert_eq!(ErrorKind::OutOfRange, S::from_vec(vec![1u8; 33]).unwrap_err().kind());
+        assert_eq!(S::Output::from(258u64), S::from_vec(vec![1u8, 2u8]).unwrap());
+        assert_eq!(usize::MAX, S::decode_to_usize(&S::encode_from_usize(usize::MAX).unwrap()).unwrap());
+        assert!(S::decode_to_usize(&S::encode_from_isize(-1isize).unwrap()).is_err());
+        assert!(S::is_encoded_null(&S::encoded_null().unwrap()).unwrap());
+        assert!(!S::is_encoded_null(&S::zero_center()).unwrap());
+    }
+
+    #[test]
+    fn decode_test() {
+        for_each_scalar!(decode());
+    }
+}
@@ -0,0 +1,139 @@
+use super::AttributeEncoder;
+use crate::errors::{Error, ErrorKind};
+
+use blake2::{Blake2s, VarBlake2b};
+use digest::{Digest, Input, VariableOutput};
+use serde::{Deserialize, Serialize};
+use sha3::{Keccak256, Sha3_256};
+use std::{fmt, str::FromStr};
+
+/// The number of bytes every algorithm outputs
+const DIGEST_SIZE: usize = 32;
+
+/// The hash algorithms that can be selected at runtime to encode strings.
+/// Each serializes as its identifier so it can be stored with encoded credentials
+#[derive(Copy, Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum HashAlgorithm {
+    /// SHA-256 from FIPS 180-4, the default
+    #[default]
+    #[serde(rename = "sha-256")]
+    Sha256,
+    /// SHA3-256 from FIPS 202
+    #[serde(rename = "sha3-256")]
+    Sha3_256,
+    /// BLAKE2s with a 32 byte output
+    #[serde(rename = "blake2s-256")]
+    Blake2s256,
+    /// BLAKE2b parameterized for a 32 byte output.
+    /// This is not the same as truncating BLAKE2b-512
+    #[serde(rename = "blake2b-256")]
+    Blake2b256,
+    /// The original Keccak submission with a 32 byte output as used by Ethereum
+    #[serde(rename = "keccak-256")]
+    Keccak256,
+}
+
+impl HashAlgorithm {
+    /// Every supported algorithm
+    pub const ALL: [HashAlgorithm; 5] = [
+        HashAlgorithm::Sha256,
+        HashAlgorithm::Sha3_256,
+        HashAlgorithm::Blake2s256,
+        HashAlgorithm::Blake2b256,
+        HashAlgorithm::Keccak256,
+    ];
+
+    /// The identifier used when storing or parsing this algorithm
+    pub fn identifier(self) -> &'static str {
+        match self {
+            HashAlgorithm::Sha256 => "sha-256",
+            HashAlgorithm::Sha3_256 => "sha3-256",
+            HashAlgorithm::Blake2s256 => "blake2s-256",
+            HashAlgorithm::Blake2b256 => "blake2b-256",
+            HashAlgorithm::Keccak256 => "keccak-256",
+        }
+    }
+
+    /// Hash `data` into 32 bytes
+    pub fn digest(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            HashAlgorithm::Sha256 => sha2::Sha256::digest(data).to_vec(),
+            HashAlgorithm::Sha3_256 => Sha3_256::digest(data).to_vec(),
+            HashAlgorithm::Blake2s256 => Blake2s::digest(data).to_vec(),
+            HashAlgorithm::Blake2b256 => {
+                let mut hasher = VarBlake2b::new(DIGEST_SIZE).expect("32 bytes is a valid BLAKE2b output size");
+                hasher.input(data);
+                let mut output = Vec::with_capacity(DIGEST_SIZE);
+                hasher.variable_result(|res| output.extend_from_slice(res));
+                output
+            },
+            HashAlgorithm::Keccak256 => Keccak256::digest(data).to_vec(),
+        }
+    }
+
+    /// Encode a UTF-8 string as its hash with this algorithm.
+    /// `HashAlgorithm::Sha256.encode::<E>(value)` is the same as
+    /// `E::encode_from_utf8_as_hash::<_, sha2::Sha256>(value)`
+    pub fn encode<E: AttributeEncoder + ?Sized>(self, value: &str) -> Result<E::Output, Error> {
+        E::from_vec(self.digest(value.as_bytes()))
+    }
+}
+
+impl fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.identifier())
+    }
+}
+
+impl FromStr for HashAlgorithm {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        HashAlgorithm::ALL
+            .iter()
+            .find(|a| a.identifier() == s)
+            .copied()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, format!("'{}' is not a supported hash algorithm", s)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digest_test() {
+        let expected = [
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532",
+            "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982",
+            "bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319",
+            "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45",
+        ];
+        for (algorithm, expected) in HashAlgorithm::ALL.iter().zip(expected.iter()) {
+            assert_eq!(*expected, hex::encode(algorithm.digest(b"abc")), "{}", algorithm);
+        }
+    }
+
+    #[test]
+    fn identifier_test() {
+        for algorithm in HashAlgorithm::ALL.iter() {
+            assert_eq!(*algorithm, HashAlgorithm::from_str(algorithm.identifier()).unwrap());
+            let json = serde_json::to_string(algorithm).unwrap();
+            assert_eq!(format!("\"{}\"", algorithm), json);
+            assert_eq!(*algorithm, serde_json::from_str::<HashAlgorithm>(json.as_str()).unwrap());
+        }
+        assert_eq!(ErrorKind::InvalidInput, HashAlgorithm::from_str("SHA256").unwrap_err().kind());
+        assert_eq!(HashAlgorithm::Sha256, HashAlgorithm::default());
+    }
+
+    #[cfg(feature = "rsa-pure")]
+    #[test]
+    fn encode_test() {
+        use crate::encoding::rsa_pure::BigInteger;
+
+        assert_eq!(BigInteger::encode_from_utf8_as_hash::<_, sha2::Sha256>("Alice").unwrap(), HashAlgorithm::Sha256.encode::<BigInteger>("Alice").unwrap());
+        assert_eq!(BigInteger::encode_from_utf8_as_hash::<_, Keccak256>("Alice").unwrap(), HashAlgorithm::Keccak256.encode::<BigInteger>("Alice").unwrap());
+        assert_ne!(HashAlgorithm::Sha256.encode::<BigInteger>("Alice").unwrap(), HashAlgorithm::Sha3_256.encode::<BigInteger>("Alice").unwrap());
+    }
+}
@@ -0,0 +1,144 @@
+use super::{to_bigint, AttributeDecoder};
+use crate::errors::{Error, ErrorKind};
+
+use digest::{BlockInput, Digest, generic_array::typenum::Unsigned};
+use num_bigint::{BigInt, Sign};
+use num_traits::Zero;
+
+/// Prepended to the attribute name to form the domain separation tag
+pub const DST_PREFIX: &str = "ARIES-CREDX-V01-HASH-TO-FIELD-";
+
+/// The security level in bits the output must be statistically close to uniform for
+const SECURITY_BITS: usize = 128;
+
+/// The lowest value that is not a sentinel. Null is 7 and -infinity is 8
+const LOWEST: u64 = 9;
+
+/// The number of values at the top of the range reserved for the sentinels,
+/// i.e. +infinity at `max() - 9` and NaN at `max() - 8`, plus the
+/// values above them
+const RESERVED_TOP: u64 = 9;
+
+/// Expand `msg` into `len_in_bytes` uniformly random bytes with `expand_message_xmd`
+/// from RFC 9380 section 5.3.1.
+/// `dst` longer than 255 bytes is hashed as described in section 5.3.3
+pub fn expand_message_xmd<D: Digest + BlockInput>(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Result<Vec<u8>, Error> {
+    let b_in_bytes = D::OutputSize::to_usize();
+    let s_in_bytes = D::BlockSize::to_usize();
+    let ell = len_in_bytes.div_ceil(b_in_bytes);
+    if ell > 255 || len_in_bytes > 65535 || len_in_bytes == 0 {
+        return Err(Error::new(ErrorKind::InvalidInput, format!("Cannot expand to {} bytes", len_in_bytes)));
+    }
+    let mut dst_prime = if dst.len() > 255 {
+        D::new().chain(b"H2C-OVERSIZE-DST-").chain(dst).result().to_vec()
+    } else {
+        dst.to_vec()
+    };
+    dst_prime.push(dst_prime.len() as u8);
+
+    let b_0 = D::new()
+        .chain(vec![0u8; s_in_bytes])
+        .chain(msg)
+        .chain((len_in_bytes as u16).to_be_bytes())
+        .chain([0u8])
+        .chain(&dst_prime)
+        .result();
+    let mut b_i = D::new().chain(&b_0).chain([1u8]).chain(&dst_prime).result();
+    let mut uniform_bytes = b_i.to_vec();
+    for i in 2..=ell {
+        let xored = b_0.iter().zip(b_i.iter()).map(|(a, b)| a ^ b).collect::<Vec<u8>>();
+        b_i = D::new().chain(xored).chain([i as u8]).chain(&dst_prime).result();
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+    uniform_bytes.truncate(len_in_bytes);
+    Ok(uniform_bytes)
+}
+
+/// The domain separation tag used for `attribute`
+pub fn attribute_dst(attribute: &str) -> Vec<u8> {
+    let mut dst = DST_PREFIX.as_bytes().to_vec();
+    dst.extend_from_slice(attribute.as_bytes());
+    dst
+}
+
+/// Hash a UTF-8 string to a value for `E` that is uniformly distributed between
+/// the sentinels, so it can never be mistaken for null, infinity or NaN.
+/// `attribute` is the attribute name used for domain separation so the same
+/// string hashes to unrelated values in different attributes.
+///
+/// Like `hash_to_field` in RFC 9380 section 5.2 the expanded message has
+/// `SECURITY_BITS` more bits than the range so the bias from reducing it is negligible.
+///
+/// An example call is encode_from_utf8_to_field::<FieldElement, sha2::Sha256>("Alice", "first_name")
+pub fn encode_from_utf8_to_field<E, D>(value: &str, attribute: &str) -> Result<E::Output, Error>
+    where E: AttributeDecoder + ?Sized,
+          D: Digest + BlockInput {
+    let top = top::<E>()?;
+    let range = &top - BigInt::from(LOWEST + RESERVED_TOP);
+    let len_in_bytes = (range.bits() + SECURITY_BITS).div_ceil(8);
+    let uniform_bytes = expand_message_xmd::<D>(value.as_bytes(), attribute_dst(attribute).as_slice(), len_in_bytes)?;
+    let n = BigInt::from_bytes_be(Sign::Plus, uniform_bytes.as_slice()) % range + BigInt::from(LOWEST);
+    E::from_vec(n.to_bytes_be().1)
+}
+
+/// The integer that `max()` represents. Backends where `max()` is the
+/// group order wrap it to zero, so it is recovered as one above -1
+pub(super) fn top<E: AttributeDecoder + ?Sized>() -> Result<BigInt, Error> {
+    let max = to_bigint::<E>(&E::max());
+    if max.is_zero() {
+        Ok(to_bigint::<E>(&E::try_neg(E::Output::from(1u64))?) + 1)
+    } else {
+        Ok(max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_message_xmd_test() {
+        // RFC 9380 appendix K.1
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        let res = expand_message_xmd::<sha2::Sha256>(b"", dst, 0x20).unwrap();
+        assert_eq!("68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235", hex::encode(res));
+        let res = expand_message_xmd::<sha2::Sha256>(b"abc", dst, 0x20).unwrap();
+        assert_eq!("d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615", hex::encode(res));
+        let res = expand_message_xmd::<sha2::Sha256>(b"", dst, 0x80).unwrap();
+        assert_eq!("af84c27ccfd45d41914fdff5df25293e221afc53d8ad2ac06d5e3e29485dadbee0d121587713a3e0dd4d5e69e93eb7cd4f5df4cd103e188cf60cb02edc3edf18eda8576c412b18ffb658e3dd6ec849469b979d444cf7b26911a08e63cf31f9dcc541708d3491184472c2c29bb749d4286b004ceb5ee6b9a7fa5b646c993f0ced", hex::encode(res));
+
+        assert_eq!(ErrorKind::InvalidInput, expand_message_xmd::<sha2::Sha256>(b"", dst, 0).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidInput, expand_message_xmd::<sha2::Sha256>(b"", dst, 255 * 32 + 1).unwrap_err().kind());
+        let long_dst = vec![b'a'; 256];
+        assert_eq!(32, expand_message_xmd::<sha2::Sha256>(b"", long_dst.as_slice(), 32).unwrap().len());
+    }
+
+    #[cfg(feature = "rsa-pure")]
+    #[test]
+    fn encode_test() {
+        use crate::encoding::{rsa_pure::BigInteger, AttributeEncoder};
+
+        let res = encode_from_utf8_to_field::<BigInteger, sha2::Sha256>("Alice", "first_name").unwrap();
+        assert_eq!(res, encode_from_utf8_to_field::<BigInteger, sha2::Sha256>("Alice", "first_name").unwrap());
+        assert_ne!(res, encode_from_utf8_to_field::<BigInteger, sha2::Sha256>("Alice", "last_name").unwrap());
+        assert_ne!(res, BigInteger::encode_from_utf8_as_hash::<_, sha2::Sha256>("Alice").unwrap());
+        assert!(res > BigInteger::encode_from_f64(f64::NEG_INFINITY).unwrap());
+        assert!(res < BigInteger::encode_from_f64(f64::INFINITY).unwrap());
+    }
+
+    #[cfg(feature = "bls381")]
+    #[test]
+    fn field_test() {
+        use crate::encoding::AttributeEncoder;
+        use amcl_wrapper::field_elem::FieldElement;
+
+        assert_eq!(to_bigint::<FieldElement>(&FieldElement::encode_from_f64(f64::NAN).unwrap()) + 8, top::<FieldElement>().unwrap());
+        let neg_inf = to_bigint::<FieldElement>(&FieldElement::encode_from_f64(f64::NEG_INFINITY).unwrap());
+        let pos_inf = to_bigint::<FieldElement>(&FieldElement::encode_from_f64(f64::INFINITY).unwrap());
+        for i in 0..64 {
+            let res = encode_from_utf8_to_field::<FieldElement, sha2::Sha256>(i.to_string().as_str(), "age").unwrap();
+            let n = to_bigint::<FieldElement>(&res);
+            assert!(n > neg_inf && n < pos_inf);
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use super::{to_bigint, AttributeDecoder, AttributeEncoder};
+use crate::errors::{Error, ErrorKind};
+
+use digest::Digest;
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+
+/// A credential attribute as it appears in Indy and legacy AnonCreds credentials
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AttributeValue {
+    /// The value as provided by the issuer
+    pub raw: String,
+    /// The decimal string signed by the issuer
+    pub encoded: String,
+}
+
+impl AttributeValue {
+    /// Create the raw and encoded pair for `raw`
+    pub fn new<A: Into<String>>(raw: A) -> Self {
+        let raw = raw.into();
+        let encoded = encode(raw.as_str());
+        Self { raw, encoded }
+    }
+
+    /// Check that `encoded` is the Indy encoding of `raw`
+    pub fn is_valid(&self) -> bool {
+        encode(self.raw.as_str()) == self.encoded
+    }
+
+    /// Convert the encoded value to `E` as described in `encoded_to_backend`
+    pub fn to_backend<E: AttributeEncoder>(&self) -> Result<E::Output, Error> {
+        encoded_to_backend::<E>(self.encoded.as_str())
+    }
+}
+
+/// Compute the Indy `encoded` value for `raw`.
+/// Values that Python's `int(str(raw))` reads as a 32-bit signed integer are
+/// encoded as that integer, everything else as the SHA-256 hash of the UTF-8
+/// bytes read as a big-endian integer.
+/// Unlike Indy, integers written with non-ASCII digits are hashed
+pub fn encode(raw: &str) -> String {
+    match parse_int(raw) {
+        Some(i) => i.to_string(),
+        None => BigUint::from_bytes_be(&sha2::Sha256::digest(raw.as_bytes())).to_str_radix(10),
+    }
+}
+
+/// Parse `raw` like Python's `int`, which the Indy reference implementation uses.
+/// Surrounding whitespace is ignored, a `+` or `-` sign is allowed and single
+/// underscores may separate digits, i.e. " +1_000 " is 1000.
+/// Python also accepts non-ASCII decimal digits, which are hashed here instead
+fn parse_int(raw: &str) -> Option<i32> {
+    let value = raw.trim_matches(|c: char| c.is_whitespace() || ('\x1c'..='\x1f').contains(&c));
+    let (sign, digits) = match value.as_bytes().first() {
+        Some(b'-') => ("-", &value[1..]),
+        Some(b'+') => ("", &value[1..]),
+        _ => ("", value),
+    };
+    let separated = digits.split('_').all(|group| !group.is_empty() && group.bytes().all(|b| b.is_ascii_digit()));
+    if !separated {
+        return None;
+    }
+    BigInt::parse_bytes(format!("{}{}", sign, digits.replace('_', "")).as_bytes(), 10)?.to_i32()
+}
+
+/// Convert an Indy `encoded` value to the equivalent zero centered value for `E`.
+/// 32-bit integers become `encode_from_i32` and hashes become the value
+/// `encode_from_utf8_as_hash::<_, sha2::Sha256>` produces for the same raw value.
+/// Fails if `encoded` is not a decimal integer that Indy could have produced,
+/// which includes any value not written in its canonical form like "007" or "+7"
+pub fn encoded_to_backend<E: AttributeEncoder + ?Sized>(encoded: &str) -> Result<E::Output, Error> {
+    let invalid = || Error::new(ErrorKind::InvalidInput, format!("'{}' is not an Indy encoded value", encoded));
+    let value = BigInt::parse_bytes(encoded.as_bytes(), 10).ok_or_else(invalid)?;
+    if value.to_string() != encoded {
+        return Err(invalid());
+    }
+    if let Some(i) = value.to_i32() {
+        return E::encode_from_i32(i);
+    }
+    match value.to_bytes_be() {
+        (Sign::Plus, bytes) if bytes.len() <= 32 => E::from_vec(bytes),
+        _ => Err(invalid()),
+    }
+}
+
+/// Convert a value created by `encoded_to_backend` back to the Indy `encoded` value.
+/// Backends with an order below 2^256 reduce hashes when encoding so the
+/// result only matches the original hash for the RSA backends
+pub fn backend_to_encoded<D: AttributeDecoder + ?Sized>(value: &D::Output) -> Result<String, Error> {
+    let v = to_bigint::<D>(value);
+    let offset = &v - to_bigint::<D>(&D::zero_center());
+    Ok(match offset.to_i32() {
+        Some(i) => i.to_string(),
+        None => v.to_str_radix(10),
+    })
+}
+
+#[cfg(all(test, feature = "rsa-pure"))]
+mod tests {
+    use super::*;
+    use crate::encoding::rsa_pure::BigInteger;
+
+    #[test]
+    fn encode_test() {
+        assert_eq!("27034640024117331033063128044004318218486816931520886405535659934417438781507", encode("Alice"));
+        assert_eq!("102987336249554097029535212322581322789799900648198034993379397001115665086549", encode(""));
+        assert_eq!("54563620706161386386769919550506899497423339035758246241599789538076765579124", encode("101.5"));
+        assert_eq!("26221484005389514539852548961319751347124425277437769688639924217837557266135", encode("2147483648"));
+        assert_eq!("2147483647", encode("2147483647"));
+        assert_eq!("-2147483648", encode("-2147483648"));
+        assert_eq!("7", encode("007"));
+        assert_eq!("0", encode("-0"));
+        assert_eq!("5", encode(" 5\n"));
+        assert_eq!("1000", encode("+1_000"));
+        assert_eq!("-1000000", encode("-1_000_000"));
+        assert_eq!("9549492211045426870145197483426705128319300057914682412912699026504644008555", encode("1__000"));
+        assert_eq!("32018596334452580715878307930168022169359994595276213398638283616398903085946", encode("_1"));
+        assert_eq!("6297299010768344681062767717528381200205121598323980164761278868275022747496", encode("1_"));
+        assert_eq!("35881685407493473979154562396029980686445152256582547976682859355519377651753", encode("- 5"));
+        // Python reads the Arabic-Indic digit one as 1
+        assert_eq!("44580073654276457668985274285437644055048304415721565290443239071962249151355", encode("\u{661}"));
+    }
+
+    #[test]
+    fn attribute_value_test() {
+        let value = AttributeValue::new("Alice");
+        assert!(value.is_valid());
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(r#"{"raw":"Alice","encoded":"27034640024117331033063128044004318218486816931520886405535659934417438781507"}"#, json);
+        assert_eq!(value, serde_json::from_str(json.as_str()).unwrap());
+        let forged = AttributeValue { raw: "Bob".to_string(), encoded: value.encoded };
+        assert!(!forged.is_valid());
+    }
+
+    #[test]
+    fn backend_test() {
+        let value = AttributeValue::new("Alice");
+        let res = value.to_backend::<BigInteger>().unwrap();
+        assert_eq!(BigInteger::encode_from_utf8_as_hash::<_, sha2::Sha256>("Alice").unwrap(), res);
+        assert_eq!(value.encoded, backend_to_encoded::<BigInteger>(&res).unwrap());
+
+        let value = AttributeValue::new("-42");
+        let res = value.to_backend::<BigInteger>().unwrap();
+        assert_eq!(BigInteger::encode_from_i32(-42i32).unwrap(), res);
+        assert_eq!("-42", backend_to_encoded::<BigInteger>(&res).unwrap());
+
+        for encoded in ["", "-", "+5", "007", "-0", " 5", "1_000", "1.5", "0x10", "-2147483649", "115792089237316195423570985008687907853269984665640564039457584007913129639936"].iter() {
+            assert_eq!(ErrorKind::InvalidInput, encoded_to_backend::<BigInteger>(encoded).unwrap_err().kind());
+        }
+    }
+
+    #[cfg(feature = "bls381")]
+    #[test]
+    fn field_backend_test() {
+        use amcl_wrapper::field_elem::FieldElement;
+
+        let value = AttributeValue::new("30");
+        let res = value.to_backend::<FieldElement>().unwrap();
+        assert_eq!(FieldElement::encode_from_i32(30i32).unwrap(), res);
+        assert_eq!("30", backend_to_encoded::<FieldElement>(&res).unwrap());
+    }
+}
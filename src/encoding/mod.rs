@@ -1,9 +1,15 @@
-use chrono::DateTime;
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, TimeZone, Timelike, Utc};
 use digest::{Digest, generic_array::typenum::U32};
-use std::ops::{Add, Sub, Neg};
+use num_bigint::{BigInt, Sign};
+use num_traits::{Signed, ToPrimitive};
+use std::{ops::{Add, Sub, Neg}, str::FromStr};
 
-/// How many bits are used to shift 1 to get to zero centering
-const BITS_IN_ZERO: usize = 254;
+use crate::errors::{Error, ErrorKind};
+
+/// The fewest bits every backend must allow on either side of `zero_center()`
+/// so that all 64-bit integers, dates and floats can be encoded
+pub const MIN_OFFSET_BITS: usize = 64;
 
 /// Represents an abstract encoder used for converting types to cryptographic integers
 /// Cryptographic integers are limited to 256 bits
@@ -11,36 +17,125 @@ pub trait AttributeEncoder {
     /// The type to represent the cryptographic integer
     type Output: Add<Output = Self::Output> + Sub<Output = Self::Output> + Neg<Output = Self::Output> + From<u64>;
 
-    /// Return the highest value for `Output`
+    /// How many bits are used to shift 1 to get to zero centering.
+    /// Backends whose order is below 2^`BITS_IN_ZERO` use it reduced modulo the order
+    const BITS_IN_ZERO: usize;
+
+    /// The most bits a value above `zero_center()` may use
+    /// without wrapping around the modulus or reaching the +infinity sentinel.
+    /// Must be at least `MIN_OFFSET_BITS`
+    const MAX_POSITIVE_BITS: usize = Self::BITS_IN_ZERO - 1;
+
+    /// The most bits a value below `zero_center()` may use
+    /// without reaching the -infinity sentinel.
+    /// Must be at least `MIN_OFFSET_BITS`
+    const MAX_NEGATIVE_BITS: usize = Self::BITS_IN_ZERO - 1;
+
+    /// How many of the 63 magnitude bits of an `f64` are kept by `encode_from_f64`.
+    /// Lower values drop the least significant mantissa bits which keeps the
+    /// encoding order preserving but no longer injective
+    const FLOAT_PRECISION_BITS: usize = 63;
+
+    /// Return the highest value for `Output`.
+    /// Backends over a prime order group or field return the order itself,
+    /// which is zero as an element, so `max() - n` is the same as `order - n`
     fn max() -> Self::Output;
     /// Return what a value that represents zero
     fn zero_center() -> Self::Output;
-    /// Takes a vector of bytes and returns `Self::Output`
-    fn from_vec(v: Vec<u8>) -> Self::Output;
+    /// Takes a vector of bytes and returns `Self::Output`.
+    /// Fails if the bytes do not fit in `Self::Output`
+    fn from_vec(v: Vec<u8>) -> Result<Self::Output, Error>;
+
+    /// Computes `lhs + rhs`.
+    /// Backends whose arithmetic can fail should override this
+    fn try_add(lhs: Self::Output, rhs: Self::Output) -> Result<Self::Output, Error> {
+        Ok(lhs + rhs)
+    }
+
+    /// Computes `lhs - rhs`.
+    /// Backends whose arithmetic can fail should override this
+    fn try_sub(lhs: Self::Output, rhs: Self::Output) -> Result<Self::Output, Error> {
+        Ok(lhs - rhs)
+    }
+
+    /// Computes `-value`.
+    /// Backends whose arithmetic can fail should override this
+    fn try_neg(value: Self::Output) -> Result<Self::Output, Error> {
+        Ok(-value)
+    }
 
     /// Encoded value to represent NULL values.
     /// Should indicate a value was not available
-    fn encoded_null() -> Result<Self::Output, String> {
+    fn encoded_null() -> Result<Self::Output, Error> {
         let mut null = vec![0u8; 32];
         null[31] = 7;
-        Ok(Self::from_vec(null))
+        Self::from_vec(null)
     }
 
     /// Takes an date string that is formatted according to RFC3339
     /// and converts it to a cryptographic integer. 
     /// `value`: Any type that can be converted into a string slice
-    fn encode_from_rfc3339_as_unixtimestamp<'a, A: Into<&'a str>>(value: A) -> Result<Self::Output, String> {
-        let dt = DateTime::parse_from_rfc3339(value.into()).map_err(|e| format!("{:?}", e))?;
-        Ok(Self::zero_center() + Self::Output::from(dt.timestamp() as u64))
+    ///
+    /// Dates before 1970 are encoded below `zero_center()`
+    fn encode_from_rfc3339_as_unixtimestamp<'a, A: Into<&'a str>>(value: A) -> Result<Self::Output, Error> {
+        let dt = DateTime::parse_from_rfc3339(value.into())?;
+        encode_offset::<Self>(dt.timestamp())
+    }
+
+    /// Takes an date string that is formatted according to RFC3339
+    /// and converts it to a cryptographic integer of milliseconds since the unix epoch.
+    /// `value`: Any type that can be converted into a string slice
+    ///
+    /// Sub-millisecond digits are dropped so the encoding is order preserving
+    /// but two instants in the same millisecond encode the same
+    fn encode_from_rfc3339_as_unixtimestamp_millis<'a, A: Into<&'a str>>(value: A) -> Result<Self::Output, Error> {
+        let dt = DateTime::parse_from_rfc3339(value.into())?;
+        encode_offset::<Self>(dt.timestamp_millis())
     }
 
     /// Takes an date string that is formatted according to RFC3339
     /// and converts it to a cryptographic integer. 
     /// `value`: Any type that can be converted into a string slice
-    fn encode_from_rfc3339_as_dayssince1900<'a, A: Into<&'a str>>(value: A) -> Result<Self::Output, String> {
-        let dt = DateTime::parse_from_rfc3339(value.into()).map_err(|e| format!("{:?}", e))?;
-        let base = DateTime::parse_from_rfc3339("1900-01-01T00:00:00.000+00:00").map_err(|e| format!("{:?}", e))?;
-        Ok(Self::zero_center() + Self::Output::from((dt - base).num_days() as u64))
+    ///
+    /// Partial days are rounded down so 1899-12-31T23:59:59Z is encoded as
+    /// the day before 1900-01-01
+    fn encode_from_rfc3339_as_dayssince1900<'a, A: Into<&'a str>>(value: A) -> Result<Self::Output, Error> {
+        let dt = DateTime::parse_from_rfc3339(value.into())?;
+        encode_offset::<Self>(days_since_1900(&dt))
+    }
+
+    /// Takes an ISO 8601 calendar date without a time, i.e. a birthdate like
+    /// 2003-10-16, and converts it to a cryptographic integer.
+    /// `value`: Any type that can be converted into a string slice
+    ///
+    /// The result is the same as `encode_from_rfc3339_as_dayssince1900` for
+    /// midnight UTC on that date and is decoded with `decode_dayssince1900_to_date`
+    fn encode_from_iso8601_date_as_dayssince1900<'a, A: Into<&'a str>>(value: A) -> Result<Self::Output, Error> {
+        let date = NaiveDate::parse_from_str(value.into(), "%Y-%m-%d")?;
+        encode_offset::<Self>(date.signed_duration_since(epoch_1900()).num_days())
+    }
+
+    /// Takes an ISO 8601 time of day without an offset, formatted as
+    /// `hh:mm`, `hh:mm:ss` or `hh:mm:ss.sss`, and converts it to a
+    /// cryptographic integer of milliseconds since midnight.
+    /// `value`: Any type that can be converted into a string slice
+    fn encode_from_iso8601_time_as_milliseconds<'a, A: Into<&'a str>>(value: A) -> Result<Self::Output, Error> {
+        let value = value.into();
+        let time = NaiveTime::parse_from_str(value, "%H:%M:%S%.f")
+            .or_else(|_| NaiveTime::parse_from_str(value, "%H:%M"))?;
+        // Leap seconds have nanoseconds past 1_000_000_000 so they sort after :59
+        let millis = i64::from(time.num_seconds_from_midnight()) * 1000 + i64::from(time.nanosecond() / 1_000_000);
+        encode_offset::<Self>(millis)
+    }
+
+    /// Takes an ISO 8601 duration like `P1DT12H` or `-PT0.5S` and converts
+    /// it to a cryptographic integer of milliseconds.
+    /// `value`: Any type that can be converted into a string slice
+    ///
+    /// Only weeks, days, hours, minutes and seconds are allowed because years and months
+    /// do not have a fixed length. Sub-millisecond digits are dropped
+    fn encode_from_iso8601_duration_as_milliseconds<'a, A: Into<&'a str>>(value: A) -> Result<Self::Output, Error> {
+        encode_offset::<Self>(parse_iso8601_duration(value.into())?)
     }
 
     /// Takes a UTF-8 encoded string and uses the Blake2 hash to convert
@@ -49,86 +144,910 @@ pub trait AttributeEncoder {
     /// The hash can be anything that emits a 32 byte output.
     /// 
     /// An example call is encode_from_utf8_as_hash::<sha2::Sha256>("first_name")
-    fn encode_from_utf8_as_hash<'a, A: Into<&'a str>, D: Digest<OutputSize = U32> + Default>(value: A) -> Result<Self::Output, String> {
+    fn encode_from_utf8_as_hash<'a, A: Into<&'a str>, D: Digest<OutputSize = U32> + Default>(value: A) -> Result<Self::Output, Error> {
         let hash = D::digest(value.into().as_bytes());
-        Ok(Self::from_vec(hash[..].to_vec()))
+        Self::from_vec(hash[..].to_vec())
+    }
+
+    /// Takes a boolean and converts it into a cryptographic integer.
+    /// `false` is encoded as `zero_center()` and `true` as one above it
+    /// `value`: Any type that can be converted into a bool
+    fn encode_from_bool<A: Into<bool>>(value: A) -> Result<Self::Output, Error> {
+        Self::try_add(Self::zero_center(), Self::Output::from(value.into() as u64))
+    }
+
+    /// Takes a value from a closed enumeration and converts it into
+    /// a cryptographic integer.
+    /// `value`: The variant to encode
+    /// `variants`: The declared ordered list of all variants. Each variant is
+    /// encoded as its position in the list above `zero_center()`
+    fn encode_from_enum<T: PartialEq>(value: &T, variants: &[T]) -> Result<Self::Output, Error> {
+        check_variants(variants)?;
+        let index = variants
+            .iter()
+            .position(|v| v == value)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "Value is not one of the declared variants"))?;
+        Self::try_add(Self::zero_center(), Self::Output::from(index as u64))
     }
 
     /// Takes a 64-bit floating point number and converts it into
     /// a cryptographic integer
     /// `value`: Any type that can be converted into a f64
-    fn encode_from_f64<A: Into<f64>>(v: A) -> Result<Self::Output, String> {
+    ///
+    /// The encoding is order preserving: for any `a < b`, the encoding of `a`
+    /// is less than the encoding of `b`, so predicates like `x < 38.5` can be
+    /// proven on encoded values.
+    ///
+    /// This replaces the earlier encoding, which scaled the decimal value by 2^254
+    /// and was neither order preserving nor injective (1.0 and 0.1 had the same
+    /// encoding). Floats encoded by earlier versions must be encoded again.
+    ///
+    /// Finite values (including subnormals) are encoded as their IEEE-754
+    /// magnitude bits added to or subtracted from `zero_center()` depending on the sign.
+    /// For non-negative floats the bit pattern grows with the value, so adding it
+    /// preserves order and subtracting it reverses order for negative floats.
+    /// Both zeros encode to `zero_center()`. Only the top `FLOAT_PRECISION_BITS`
+    /// of the magnitude are kept, so every finite value lies strictly between
+    /// the sentinels which are placed as
+    ///
+    /// `encoded_null() (7) < -inf (8) < finite values < +inf (max - 9) < NaN (max - 8)`
+    ///
+    /// NaN is unordered, so it is placed above everything else.
+    fn encode_from_f64<A: Into<f64>>(v: A) -> Result<Self::Output, Error> {
         use std::num::FpCategory::*;
-        use num_bigint::Sign::*;
 
         let value = v.into();
 
-        Ok(
-            match value.classify() {
-                Nan | Subnormal => { Self::max() - Self::Output::from(8) }
-                Zero => Self::zero_center(),
-                Infinite => {
-                    if value.is_sign_positive() {
-                        Self::max() - Self::Output::from(9)
-                    } else {
-                        Self::Output::from(8)
-                    }
-                },
-                Normal => {
-                    let mut b = bigdecimal::BigDecimal::from(value);
-
-                    for _ in 0..BITS_IN_ZERO {
-                        b = b.double();
-                    }
-                    let (_, mut d) = b.clone().into_bigint_and_exponent();
-                    // 15 decimal places means no decimals at all
-                    // Anything higher should be shifted
-                    while d > 15 {
-                        b = b.half();
-                        d -= 1;
-                    }
-                    let (bi, _) = b.into_bigint_and_exponent();
-                    let (sign, bytes) = bi.to_bytes_be();
-                    match sign {
-                        NoSign => Self::zero_center(),
-                        Plus => Self::from_vec(bytes),
-                        Minus => -Self::from_vec(bytes) + Self::zero_center()
-                    }
+        match value.classify() {
+            Nan => Self::try_sub(Self::max(), Self::Output::from(8)),
+            Zero => Ok(Self::zero_center()),
+            Infinite => {
+                if value.is_sign_positive() {
+                    Self::try_sub(Self::max(), Self::Output::from(9))
+                } else {
+                    Ok(Self::Output::from(8))
+                }
+            },
+            Normal | Subnormal => {
+                let bits = value.abs().to_bits() >> float_shift::<Self>()?;
+                if value.is_sign_positive() {
+                    Self::try_add(Self::zero_center(), Self::Output::from(bits))
+                } else {
+                    Self::try_sub(Self::zero_center(), Self::Output::from(bits))
                 }
             }
-        )
+        }
+    }
+
+    /// Takes a decimal number and converts it into a fixed point
+    /// cryptographic integer `zero_center() + value * 10^scale`.
+    /// `value`: The exact decimal value
+    /// `scale`: The number of decimal places to keep, i.e. 2 for cents
+    ///
+    /// Fails if `value` has more than `scale` significant decimal places
+    /// or if the scaled value does not fit in `MAX_POSITIVE_BITS` or `MAX_NEGATIVE_BITS`.
+    /// Trailing zeros are not significant, so 1.500 fits in one decimal place.
+    fn encode_from_decimal(value: &BigDecimal, scale: u32) -> Result<Self::Output, Error> {
+        let (mut digits, mut exponent) = value.as_bigint_and_exponent();
+        if digits.sign() == Sign::NoSign {
+            return Ok(Self::zero_center());
+        }
+        let ten = BigInt::from(10);
+        while (&digits % &ten).sign() == Sign::NoSign {
+            digits /= &ten;
+            exponent -= 1;
+        }
+        let scale = i64::from(scale);
+        if exponent > scale {
+            return Err(Error::new(ErrorKind::InvalidInput, format!("{} has more than {} decimal places", value, scale)));
+        }
+        let scaled = digits * pow_of_ten::<Self>(scale - exponent)?;
+        if !fits::<Self>(&scaled) {
+            return Err(Error::new(ErrorKind::OutOfRange, format!("{} is too large to encode with {} decimal places", value, scale)));
+        }
+        Self::encode_from_bigint(&scaled)
+    }
+
+    /// Takes a decimal string like "85000.25" and converts it into a fixed point
+    /// cryptographic integer as described in `encode_from_decimal`.
+    /// `value`: Any type that can be converted into a string slice
+    /// `scale`: The number of decimal places to keep
+    fn encode_from_decimal_str<'a, A: Into<&'a str>>(value: A, scale: u32) -> Result<Self::Output, Error> {
+        let value = value.into();
+        let decimal = BigDecimal::from_str(value)
+            .map_err(|_| Error::new(ErrorKind::InvalidInput, format!("{} is not a decimal number", value)))?;
+        Self::encode_from_decimal(&decimal, scale)
     }
 
     /// Takes a signed number and converts it into
     /// a cryptographic integer
     /// `value`: Any type that can be converted into a isize
-    fn encode_from_isize<A: Into<isize>>(value: A) -> Result<Self::Output, String> {
-        let value = value.into();
-        if value < 0 {
-            if value == std::isize::MIN {
-                Ok(Self::zero_center() - Self::from_vec(value.to_be_bytes().to_vec()))
-            } else {
-                Ok(Self::zero_center() - Self::Output::from((-value) as u64))
-            }
-        } else {
-            Ok(Self::zero_center() + Self::Output::from(value as u64))
-        }
+    ///
+    /// The result is the same as `encode_from_i64` for the same number, so it
+    /// only differs between targets in which inputs fit in an `isize`
+    fn encode_from_isize<A: Into<isize>>(value: A) -> Result<Self::Output, Error> {
+        Self::encode_from_i64(value.into() as i64)
     }
 
     /// Takes an unsigned number and converts it into
     /// a cryptographic integer
     /// `value`: Any type that can be converted into a usize
-    fn encode_from_usize<A: Into<usize>>(value: A) -> Result<Self::Output, String> {
-        let value = value.into() as u64;
-        Ok(Self::zero_center() + Self::from_vec(value.to_be_bytes().to_vec()))
+    ///
+    /// The result is the same as `encode_from_u64` for the same number
+    fn encode_from_usize<A: Into<usize>>(value: A) -> Result<Self::Output, Error> {
+        Self::encode_from_u64(value.into() as u64)
+    }
+
+    /// Takes a signed 8-bit number and converts it into a cryptographic integer.
+    /// Every fixed width encoder produces the same result for the same number on every target
+    fn encode_from_i8(value: i8) -> Result<Self::Output, Error> {
+        Self::encode_from_i64(i64::from(value))
+    }
+
+    /// Takes a signed 16-bit number and converts it into a cryptographic integer
+    fn encode_from_i16(value: i16) -> Result<Self::Output, Error> {
+        Self::encode_from_i64(i64::from(value))
+    }
+
+    /// Takes a signed 32-bit number and converts it into a cryptographic integer
+    fn encode_from_i32(value: i32) -> Result<Self::Output, Error> {
+        Self::encode_from_i64(i64::from(value))
+    }
+
+    /// Takes a signed 64-bit number and converts it into a cryptographic integer
+    fn encode_from_i64(value: i64) -> Result<Self::Output, Error> {
+        encode_offset::<Self>(value)
+    }
+
+    /// Takes an unsigned 8-bit number and converts it into a cryptographic integer
+    fn encode_from_u8(value: u8) -> Result<Self::Output, Error> {
+        Self::encode_from_u64(u64::from(value))
+    }
+
+    /// Takes an unsigned 16-bit number and converts it into a cryptographic integer
+    fn encode_from_u16(value: u16) -> Result<Self::Output, Error> {
+        Self::encode_from_u64(u64::from(value))
+    }
+
+    /// Takes an unsigned 32-bit number and converts it into a cryptographic integer
+    fn encode_from_u32(value: u32) -> Result<Self::Output, Error> {
+        Self::encode_from_u64(u64::from(value))
+    }
+
+    /// Takes an unsigned 64-bit number and converts it into a cryptographic integer
+    fn encode_from_u64(value: u64) -> Result<Self::Output, Error> {
+        Self::try_add(Self::zero_center(), from_u64::<Self>(value)?)
+    }
+
+    /// Takes a signed 128-bit number and converts it into
+    /// a cryptographic integer
+    /// `value`: Any type that can be converted into a i128
+    fn encode_from_i128<A: Into<i128>>(value: A) -> Result<Self::Output, Error> {
+        Self::encode_from_bigint(&BigInt::from(value.into()))
+    }
+
+    /// Takes an unsigned 128-bit number and converts it into
+    /// a cryptographic integer
+    /// `value`: Any type that can be converted into a u128
+    fn encode_from_u128<A: Into<u128>>(value: A) -> Result<Self::Output, Error> {
+        Self::encode_from_bigint(&BigInt::from(value.into()))
+    }
+
+    /// Takes an arbitrary precision integer and converts it into
+    /// a cryptographic integer.
+    /// Fails if `value` needs more than `MAX_POSITIVE_BITS` or `MAX_NEGATIVE_BITS`
+    fn encode_from_bigint(value: &BigInt) -> Result<Self::Output, Error> {
+        if !fits::<Self>(value) {
+            return Err(Error::new(ErrorKind::OutOfRange, format!("{} does not fit in {} bits", value, match value.sign() {
+                Sign::Minus => Self::MAX_NEGATIVE_BITS,
+                _ => Self::MAX_POSITIVE_BITS,
+            })));
+        }
+        let (sign, bytes) = value.to_bytes_be();
+        match sign {
+            Sign::Minus => Self::try_sub(Self::zero_center(), Self::from_vec(bytes)?),
+            _ => Self::try_add(Self::zero_center(), Self::from_vec(bytes)?),
+        }
+    }
+}
+
+
+/// Represents the reverse of an `AttributeEncoder` used by verifiers to
+/// convert revealed cryptographic integers back into their original values.
+/// Hashed values cannot be decoded.
+pub trait AttributeDecoder: AttributeEncoder {
+    /// Takes `Self::Output` and returns its big-endian bytes.
+    /// This is the inverse of `from_vec`
+    fn to_vec(value: &Self::Output) -> Vec<u8>;
+
+    /// Checks whether `value` is the encoded NULL value
+    fn is_encoded_null(value: &Self::Output) -> Result<bool, Error> {
+        Ok(to_bigint::<Self>(value) == to_bigint::<Self>(&Self::encoded_null()?))
+    }
+
+    /// Takes a cryptographic integer created by `encode_from_rfc3339_as_unixtimestamp`
+    /// and converts it back to a UTC date time.
+    fn decode_unixtimestamp_to_datetime(value: &Self::Output) -> Result<DateTime<Utc>, Error> {
+        let seconds = offset_from_zero_center::<Self>(value)
+            .to_i64()
+            .ok_or_else(|| Error::new(ErrorKind::OutOfRange, "Encoded value is not a valid unix timestamp"))?;
+        Utc.timestamp_opt(seconds, 0)
+            .single()
+            .ok_or_else(|| Error::new(ErrorKind::OutOfRange, format!("Unix timestamp {} is out of range", seconds)))
+    }
+
+    /// Takes a cryptographic integer created by `encode_from_rfc3339_as_unixtimestamp_millis`
+    /// and converts it back to a UTC date time.
+    fn decode_unixtimestamp_millis_to_datetime(value: &Self::Output) -> Result<DateTime<Utc>, Error> {
+        let millis = offset_from_zero_center::<Self>(value)
+            .to_i64()
+            .ok_or_else(|| Error::new(ErrorKind::OutOfRange, "Encoded value is not a valid unix timestamp"))?;
+        Utc.timestamp_millis_opt(millis)
+            .single()
+            .ok_or_else(|| Error::new(ErrorKind::OutOfRange, format!("Unix timestamp {}ms is out of range", millis)))
+    }
+
+    /// Takes a cryptographic integer created by `encode_from_rfc3339_as_dayssince1900`
+    /// or `encode_from_iso8601_date_as_dayssince1900` and converts it back to a UTC date.
+    fn decode_dayssince1900_to_date(value: &Self::Output) -> Result<NaiveDate, Error> {
+        let days = offset_from_zero_center::<Self>(value)
+            .to_i32()
+            .ok_or_else(|| Error::new(ErrorKind::OutOfRange, "Encoded value is not a valid number of days"))?;
+        epoch_1900()
+            .checked_add_signed(Duration::days(days as i64))
+            .ok_or_else(|| Error::new(ErrorKind::OutOfRange, format!("{} days since 1900 is out of range", days)))
+    }
+
+    /// Takes a cryptographic integer created by `encode_from_iso8601_time_as_milliseconds`
+    /// and converts it back to a time of day.
+    fn decode_milliseconds_to_time(value: &Self::Output) -> Result<NaiveTime, Error> {
+        let millis = offset_from_zero_center::<Self>(value)
+            .to_u32()
+            .filter(|m| *m < (SECONDS_PER_DAY as u32 + 1) * 1000)
+            .ok_or_else(|| Error::new(ErrorKind::OutOfRange, "Encoded value is not a valid time of day"))?;
+        // A leap second is the last second of the day with extra nanoseconds
+        let seconds = (millis / 1000).min(SECONDS_PER_DAY as u32 - 1);
+        NaiveTime::from_num_seconds_from_midnight_opt(seconds, (millis - seconds * 1000) * 1_000_000)
+            .ok_or_else(|| Error::new(ErrorKind::OutOfRange, "Encoded value is not a valid time of day"))
+    }
+
+    /// Takes a cryptographic integer created by `encode_from_iso8601_duration_as_milliseconds`
+    /// and converts it back to a duration.
+    fn decode_milliseconds_to_duration(value: &Self::Output) -> Result<Duration, Error> {
+        offset_from_zero_center::<Self>(value)
+            .to_i64()
+            .and_then(Duration::try_milliseconds)
+            .ok_or_else(|| Error::new(ErrorKind::OutOfRange, "Encoded value is not a valid duration"))
+    }
+
+    /// Takes a cryptographic integer created by `encode_from_bool`
+    /// and converts it back to a boolean.
+    fn decode_to_bool(value: &Self::Output) -> Result<bool, Error> {
+        match offset_from_zero_center::<Self>(value).to_u8() {
+            Some(0) => Ok(false),
+            Some(1) => Ok(true),
+            _ => Err(Error::new(ErrorKind::OutOfRange, "Encoded value is not a boolean"))
+        }
+    }
+
+    /// Takes a cryptographic integer created by `encode_from_enum`
+    /// and returns the matching entry in `variants`.
+    /// `variants` must be the same list used to encode the value
+    fn decode_to_enum<'a, T: PartialEq>(value: &Self::Output, variants: &'a [T]) -> Result<&'a T, Error> {
+        check_variants(variants)?;
+        offset_from_zero_center::<Self>(value)
+            .to_usize()
+            .and_then(|i| variants.get(i))
+            .ok_or_else(|| Error::new(ErrorKind::OutOfRange, "Encoded value is not one of the declared variants"))
+    }
+
+    /// Takes a cryptographic integer created by `encode_from_f64`
+    /// and converts it back to a 64-bit floating point number.
+    fn decode_to_f64(value: &Self::Output) -> Result<f64, Error> {
+        let v = to_bigint::<Self>(value);
+        if v == to_bigint::<Self>(&Self::Output::from(8)) {
+            return Ok(f64::NEG_INFINITY);
+        }
+        if v == to_bigint::<Self>(&Self::try_sub(Self::max(), Self::Output::from(9))?) {
+            return Ok(f64::INFINITY);
+        }
+        if v == to_bigint::<Self>(&Self::try_sub(Self::max(), Self::Output::from(8))?) {
+            return Ok(f64::NAN);
+        }
+
+        let offset = v - to_bigint::<Self>(&Self::zero_center());
+        let shift = float_shift::<Self>()?;
+        let bits = offset
+            .abs()
+            .to_u64()
+            .filter(|b| b.leading_zeros() as usize >= shift)
+            .map(|b| b << shift)
+            .filter(|b| *b <= f64::MAX.to_bits())
+            .ok_or_else(|| Error::new(ErrorKind::OutOfRange, "Encoded value is out of range for f64"))?;
+        let result = f64::from_bits(bits);
+        Ok(if offset.sign() == Sign::Minus { -result } else { result })
+    }
+
+    /// Takes a cryptographic integer created by `encode_from_decimal`
+    /// or `encode_from_decimal_str` and converts it back to the exact decimal.
+    /// `scale` must be the same value used to encode
+    fn decode_to_decimal(value: &Self::Output, scale: u32) -> Result<BigDecimal, Error> {
+        let offset = offset_from_zero_center::<Self>(value);
+        if !fits::<Self>(&offset) {
+            return Err(Error::new(ErrorKind::OutOfRange, "Encoded value is not a fixed point decimal"));
+        }
+        Ok(BigDecimal::new(offset, i64::from(scale)))
+    }
+
+    /// Takes a cryptographic integer created by `encode_from_isize`
+    /// and converts it back to a signed number.
+    fn decode_to_isize(value: &Self::Output) -> Result<isize, Error> {
+        offset_from_zero_center::<Self>(value)
+            .to_isize()
+            .ok_or_else(|| Error::new(ErrorKind::OutOfRange, "Encoded value is out of range for isize"))
+    }
+
+    /// Takes a cryptographic integer created by `encode_from_usize`
+    /// and converts it back to an unsigned number.
+    fn decode_to_usize(value: &Self::Output) -> Result<usize, Error> {
+        offset_from_zero_center::<Self>(value)
+            .to_usize()
+            .ok_or_else(|| Error::new(ErrorKind::OutOfRange, "Encoded value is out of range for usize"))
+    }
+
+    /// Takes a cryptographic integer created by `encode_from_i8`
+    /// and converts it back to a signed 8-bit number.
+    fn decode_to_i8(value: &Self::Output) -> Result<i8, Error> {
+        offset_from_zero_center::<Self>(value)
+            .to_i8()
+            .ok_or_else(|| Error::new(ErrorKind::OutOfRange, "Encoded value is out of range for i8"))
+    }
+
+    /// Takes a cryptographic integer created by `encode_from_i16`
+    /// and converts it back to a signed 16-bit number.
+    fn decode_to_i16(value: &Self::Output) -> Result<i16, Error> {
+        offset_from_zero_center::<Self>(value)
+            .to_i16()
+            .ok_or_else(|| Error::new(ErrorKind::OutOfRange, "Encoded value is out of range for i16"))
+    }
+
+    /// Takes a cryptographic integer created by `encode_from_i32`
+    /// and converts it back to a signed 32-bit number.
+    fn decode_to_i32(value: &Self::Output) -> Result<i32, Error> {
+        offset_from_zero_center::<Self>(value)
+            .to_i32()
+            .ok_or_else(|| Error::new(ErrorKind::OutOfRange, "Encoded value is out of range for i32"))
+    }
+
+    /// Takes a cryptographic integer created by `encode_from_i64`
+    /// and converts it back to a signed 64-bit number.
+    fn decode_to_i64(value: &Self::Output) -> Result<i64, Error> {
+        offset_from_zero_center::<Self>(value)
+            .to_i64()
+            .ok_or_else(|| Error::new(ErrorKind::OutOfRange, "Encoded value is out of range for i64"))
+    }
+
+    /// Takes a cryptographic integer created by `encode_from_u8`
+    /// and converts it back to an unsigned 8-bit number.
+    fn decode_to_u8(value: &Self::Output) -> Result<u8, Error> {
+        offset_from_zero_center::<Self>(value)
+            .to_u8()
+            .ok_or_else(|| Error::new(ErrorKind::OutOfRange, "Encoded value is out of range for u8"))
+    }
+
+    /// Takes a cryptographic integer created by `encode_from_u16`
+    /// and converts it back to an unsigned 16-bit number.
+    fn decode_to_u16(value: &Self::Output) -> Result<u16, Error> {
+        offset_from_zero_center::<Self>(value)
+            .to_u16()
+            .ok_or_else(|| Error::new(ErrorKind::OutOfRange, "Encoded value is out of range for u16"))
+    }
+
+    /// Takes a cryptographic integer created by `encode_from_u32`
+    /// and converts it back to an unsigned 32-bit number.
+    fn decode_to_u32(value: &Self::Output) -> Result<u32, Error> {
+        offset_from_zero_center::<Self>(value)
+            .to_u32()
+            .ok_or_else(|| Error::new(ErrorKind::OutOfRange, "Encoded value is out of range for u32"))
+    }
+
+    /// Takes a cryptographic integer created by `encode_from_u64`
+    /// and converts it back to an unsigned 64-bit number.
+    fn decode_to_u64(value: &Self::Output) -> Result<u64, Error> {
+        offset_from_zero_center::<Self>(value)
+            .to_u64()
+            .ok_or_else(|| Error::new(ErrorKind::OutOfRange, "Encoded value is out of range for u64"))
+    }
+
+    /// Takes a cryptographic integer created by `encode_from_i128`
+    /// and converts it back to a signed 128-bit number.
+    fn decode_to_i128(value: &Self::Output) -> Result<i128, Error> {
+        offset_from_zero_center::<Self>(value)
+            .to_i128()
+            .ok_or_else(|| Error::new(ErrorKind::OutOfRange, "Encoded value is out of range for i128"))
+    }
+
+    /// Takes a cryptographic integer created by `encode_from_u128`
+    /// and converts it back to an unsigned 128-bit number.
+    fn decode_to_u128(value: &Self::Output) -> Result<u128, Error> {
+        offset_from_zero_center::<Self>(value)
+            .to_u128()
+            .ok_or_else(|| Error::new(ErrorKind::OutOfRange, "Encoded value is out of range for u128"))
+    }
+
+    /// Takes a cryptographic integer created by `encode_from_bigint`
+    /// and converts it back to an arbitrary precision integer.
+    fn decode_to_bigint(value: &Self::Output) -> Result<BigInt, Error> {
+        let offset = offset_from_zero_center::<Self>(value);
+        if !fits::<Self>(&offset) {
+            return Err(Error::new(ErrorKind::OutOfRange, "Encoded value is not an integer"));
+        }
+        Ok(offset)
+    }
+}
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// The unix timestamp of 1900-01-01T00:00:00Z
+const UNIX_TIMESTAMP_1900: i64 = -2_208_988_800;
+
+/// The date `encode_from_rfc3339_as_dayssince1900` counts from
+fn epoch_1900() -> NaiveDate {
+    NaiveDate::from_ymd_opt(1900, 1, 1).expect("1900-01-01 is a valid date")
+}
+
+/// The number of days from 1900-01-01T00:00:00Z to `dt`, rounded down
+fn days_since_1900<Tz: TimeZone>(dt: &DateTime<Tz>) -> i64 {
+    (dt.timestamp() - UNIX_TIMESTAMP_1900).div_euclid(SECONDS_PER_DAY)
+}
+
+/// Encode a signed `offset` from `zero_center()`
+fn encode_offset<A: AttributeEncoder + ?Sized>(offset: i64) -> Result<A::Output, Error> {
+    if offset < 0 {
+        A::try_sub(A::zero_center(), from_u64::<A>(offset.unsigned_abs())?)
+    } else {
+        A::try_add(A::zero_center(), from_u64::<A>(offset as u64)?)
+    }
+}
+
+/// Convert `value` to `A::Output`.
+/// Some backends treat `From<u64>` values with the top bit set as negative
+/// so those go through `from_vec`
+fn from_u64<A: AttributeEncoder + ?Sized>(value: u64) -> Result<A::Output, Error> {
+    if value > i64::MAX as u64 {
+        A::from_vec(value.to_be_bytes().to_vec())
+    } else {
+        Ok(A::Output::from(value))
+    }
+}
+
+/// Parse an ISO 8601 duration with fixed length units into milliseconds
+fn parse_iso8601_duration(value: &str) -> Result<i64, Error> {
+    let invalid = || Error::new(ErrorKind::InvalidInput, format!("{} is not an ISO 8601 duration", value));
+    let overflow = || Error::new(ErrorKind::OutOfRange, format!("{} is too long", value));
+    let (sign, rest) = match value.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, value),
+    };
+    let rest = rest.strip_prefix('P').ok_or_else(invalid)?;
+    let (date, time) = match rest.find('T') {
+        Some(i) if i + 1 < rest.len() => (&rest[..i], &rest[i + 1..]),
+        Some(_) => return Err(invalid()),
+        None => (rest, ""),
+    };
+    if date.is_empty() && time.is_empty() {
+        return Err(invalid());
+    }
+
+    let date_units: &[(char, i64)] = &[('W', 7 * SECONDS_PER_DAY * 1000), ('D', SECONDS_PER_DAY * 1000)];
+    let time_units: &[(char, i64)] = &[('H', 3_600_000), ('M', 60_000), ('S', 1000)];
+    let mut millis = 0i64;
+    for (mut part, mut units) in [(date, date_units), (time, time_units)].iter().copied() {
+        while !part.is_empty() {
+            let end = part.find(|c: char| !c.is_ascii_digit() && c != '.').ok_or_else(invalid)?;
+            let designator = part[end..].chars().next().ok_or_else(invalid)?;
+            if units == date_units && (designator == 'Y' || designator == 'M') {
+                return Err(Error::new(ErrorKind::InvalidInput, format!("{} uses years or months which do not have a fixed length", value)));
+            }
+            // Units must appear at most once and from largest to smallest
+            let position = units.iter().position(|(d, _)| *d == designator).ok_or_else(invalid)?;
+            let scale = units[position].1;
+            units = &units[position + 1..];
+
+            let (whole, fraction) = match part[..end].split_once('.') {
+                Some((whole, fraction)) if designator == 'S' && !fraction.is_empty() && fraction.bytes().all(|b| b.is_ascii_digit()) => (whole, fraction),
+                Some(_) => return Err(invalid()),
+                None => (&part[..end], ""),
+            };
+            if whole.is_empty() {
+                return Err(invalid());
+            }
+            let whole = whole.parse::<i64>().map_err(|_| overflow())?;
+            let fraction = format!("{:0<3}", &fraction[..fraction.len().min(3)]).parse::<i64>().map_err(|_| invalid())?;
+            millis = whole
+                .checked_mul(scale)
+                .and_then(|m| m.checked_add(fraction))
+                .and_then(|m| m.checked_add(millis))
+                .ok_or_else(overflow)?;
+            part = &part[end + designator.len_utf8()..];
+        }
+    }
+    Ok(sign * millis)
+}
+
+/// Ensure an enumeration declares each variant only once
+fn check_variants<T: PartialEq>(variants: &[T]) -> Result<(), Error> {
+    for (i, v) in variants.iter().enumerate() {
+        if variants[..i].contains(v) {
+            return Err(Error::new(ErrorKind::InvalidInput, format!("Variant at position {} is declared more than once", i)));
+        }
+    }
+    Ok(())
+}
+
+/// Check whether `offset` can be added to `zero_center()` without leaving the
+/// range allowed by `MAX_POSITIVE_BITS` and `MAX_NEGATIVE_BITS`
+fn fits<A: AttributeEncoder + ?Sized>(offset: &BigInt) -> bool {
+    let limit = match offset.sign() {
+        Sign::Minus => A::MAX_NEGATIVE_BITS,
+        _ => A::MAX_POSITIVE_BITS,
+    };
+    offset.bits() <= limit
+}
+
+/// The number of low bits `encode_from_f64` drops from an `f64` magnitude
+fn float_shift<A: AttributeEncoder + ?Sized>() -> Result<usize, Error> {
+    if A::FLOAT_PRECISION_BITS == 0 || A::FLOAT_PRECISION_BITS > 63 {
+        return Err(Error::new(ErrorKind::OutOfRange, format!("Float precision of {} bits is not supported", A::FLOAT_PRECISION_BITS)));
+    }
+    Ok(63 - A::FLOAT_PRECISION_BITS)
+}
+
+/// Compute 10^`exponent`, failing for exponents that could never
+/// produce a value that fits in the zero centered range
+fn pow_of_ten<A: AttributeEncoder + ?Sized>(exponent: i64) -> Result<BigInt, Error> {
+    if exponent < 0 || exponent as usize > A::MAX_POSITIVE_BITS.max(A::MAX_NEGATIVE_BITS) {
+        return Err(Error::new(ErrorKind::OutOfRange, format!("10^{} is out of range", exponent)));
     }
+    Ok(num_traits::pow(BigInt::from(10), exponent as usize))
+}
+
+/// Convert `value` to a non-negative `BigInt`
+fn to_bigint<D: AttributeDecoder + ?Sized>(value: &D::Output) -> BigInt {
+    BigInt::from_bytes_be(Sign::Plus, D::to_vec(value).as_slice())
+}
+
+/// Compute the signed distance of `value` from `zero_center()`
+fn offset_from_zero_center<D: AttributeDecoder + ?Sized>(value: &D::Output) -> BigInt {
+    to_bigint::<D>(value) - to_bigint::<D>(&D::zero_center())
 }
 
 
+/// Date parsing for formats other than RFC3339
+pub mod dates;
+
+/// Hash algorithms for encoding strings that can be selected at runtime
+#[cfg(feature = "hash")]
+pub mod hash;
+
+/// Hashing strings uniformly between the sentinels with domain separation per attribute
+pub mod hash_to_field;
+
+/// Hyperledger Indy and legacy AnonCreds raw and encoded attribute values
+#[cfg(feature = "indy")]
+pub mod indy;
+
+/// Predicate thresholds for ages and expiry dates
+pub mod predicates;
+
+/// Encoded values as hex, decimal or base64url strings, with serde support when the `serde` feature is enabled
+pub mod serialization;
+
 /// Provides an encoder to BLS12-381 FieldElements
 #[cfg(feature = "bls381")]
 pub mod bls381_fieldelem;
 
+/// The encoder shared by the arkworks scalar fields
+#[cfg(any(feature = "bls377", feature = "bn254"))]
+#[macro_use]
+mod ark_fieldelem;
+
+/// Provides an encoder to BLS12-377 scalar field elements
+#[cfg(feature = "bls377")]
+pub mod bls377_fieldelem;
+
+/// Provides an encoder to BN254 scalar field elements
+#[cfg(feature = "bn254")]
+pub mod bn254_fieldelem;
+
+/// Provides an encoder to Curve25519 scalars as used by Ristretto255
+#[cfg(feature = "ristretto255")]
+pub mod ristretto255_scalar;
+
 /// Provides an encoder to openssl's BIGNUM
 #[cfg(feature = "rsa-native")]
-pub mod rsa_native;
\ No newline at end of file
+pub mod rsa_native;
+
+/// Provides a pure Rust encoder to num-bigint's BigInt
+/// that produces the same values as `rsa_native`
+#[cfg(feature = "rsa-pure")]
+pub mod rsa_pure;
+
+/// The encoder shared by the RustCrypto elliptic curve scalars
+#[cfg(any(feature = "secp256k1", feature = "secp256r1"))]
+#[macro_use]
+mod elliptic_curve_scalar;
+
+/// Provides an encoder to secp256k1 scalars
+#[cfg(feature = "secp256k1")]
+pub mod secp256k1_scalar;
+
+/// Provides an encoder to NIST P-256 scalars
+#[cfg(feature = "secp256r1")]
+pub mod secp256r1_scalar;
+
+#[cfg(all(test, any(feature = "bls377", feature = "bls381", feature = "bn254", feature = "ristretto255", feature = "rsa-native", feature = "rsa-pure", feature = "secp256k1", feature = "secp256r1")))]
+mod conformance;
+#[cfg(all(test, any(feature = "bls377", feature = "bls381", feature = "bn254", feature = "ristretto255", feature = "rsa-native", feature = "rsa-pure", feature = "secp256k1", feature = "secp256r1")))]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::{cmp::Ordering, marker::PhantomData};
+
+    /// Call `$check::<E>($args)` for every enabled backend `E`
+    macro_rules! for_each_backend {
+        ($check:ident($($arg:expr),*)) => {{
+            #[cfg(feature = "bls377")]
+            $check::<ark_bls12_377::Fr>($($arg),*);
+            #[cfg(feature = "bls381")]
+            $check::<amcl_wrapper::field_elem::FieldElement>($($arg),*);
+            #[cfg(feature = "bn254")]
+            $check::<ark_bn254::Fr>($($arg),*);
+            #[cfg(feature = "ristretto255")]
+            $check::<curve25519_dalek::scalar::Scalar>($($arg),*);
+            #[cfg(feature = "rsa-native")]
+            $check::<rsa_native::BigNumber>($($arg),*);
+            #[cfg(feature = "rsa-pure")]
+            $check::<rsa_pure::BigInteger>($($arg),*);
+            #[cfg(feature = "secp256k1")]
+            $check::<k256::Scalar>($($arg),*);
+            #[cfg(feature = "secp256r1")]
+            $check::<p256::Scalar>($($arg),*);
+        }};
+    }
+
+    fn date_time<E: AttributeDecoder>() {
+        let res = E::encode_from_iso8601_date_as_dayssince1900("2003-10-16").unwrap();
+        assert_eq!(to_bigint::<E>(&E::encode_from_rfc3339_as_dayssince1900("2003-10-16T00:00:00Z").unwrap()), to_bigint::<E>(&res));
+        assert_eq!(NaiveDate::from_ymd_opt(2003, 10, 16).unwrap(), E::decode_dayssince1900_to_date(&res).unwrap());
+        assert!(to_bigint::<E>(&E::encode_from_iso8601_date_as_dayssince1900("1950-06-01").unwrap()) < to_bigint::<E>(&res));
+        assert_eq!(ErrorKind::InvalidDate, E::encode_from_iso8601_date_as_dayssince1900("2003-02-29").err().unwrap().kind());
+
+        let res = E::encode_from_iso8601_time_as_milliseconds("13:45:30.250").unwrap();
+        assert_eq!(BigInt::from(49_530_250), offset_from_zero_center::<E>(&res));
+        assert_eq!(NaiveTime::from_hms_milli_opt(13, 45, 30, 250).unwrap(), E::decode_milliseconds_to_time(&res).unwrap());
+        assert_eq!(BigInt::from(49_500_000), offset_from_zero_center::<E>(&E::encode_from_iso8601_time_as_milliseconds("13:45").unwrap()));
+        let leap = E::encode_from_iso8601_time_as_milliseconds("23:59:60.5").unwrap();
+        assert!(to_bigint::<E>(&leap) > to_bigint::<E>(&E::encode_from_iso8601_time_as_milliseconds("23:59:59.999").unwrap()));
+        assert_eq!(NaiveTime::from_hms_milli_opt(23, 59, 59, 1_500).unwrap(), E::decode_milliseconds_to_time(&leap).unwrap());
+        assert_eq!(ErrorKind::InvalidDate, E::encode_from_iso8601_time_as_milliseconds("24:00:01").err().unwrap().kind());
+        assert!(E::decode_milliseconds_to_time(&E::encode_from_i64(-1).unwrap()).is_err());
+
+        let res = E::encode_from_rfc3339_as_unixtimestamp_millis("1969-12-31T23:59:59.999Z").unwrap();
+        assert_eq!(BigInt::from(-1), offset_from_zero_center::<E>(&res));
+        assert_eq!("1969-12-31T23:59:59.999+00:00", E::decode_unixtimestamp_millis_to_datetime(&res).unwrap().to_rfc3339());
+        let res = E::encode_from_rfc3339_as_unixtimestamp_millis("2018-01-26T18:30:09.453+00:00").unwrap();
+        assert_eq!(BigInt::from(1_516_991_409_453u64), offset_from_zero_center::<E>(&res));
+    }
+
+    #[test]
+    fn date_time_test() {
+        for_each_backend!(date_time());
+    }
+
+    fn duration<E: AttributeDecoder>() {
+        for (input, millis) in [
+            ("P1W", 604_800_000i64),
+            ("P1DT12H", 129_600_000),
+            ("PT1M30.5S", 90_500),
+            ("PT0.0009S", 0),
+            ("-PT0.25S", -250),
+            ("P0D", 0),
+        ].iter() {
+            let res = E::encode_from_iso8601_duration_as_milliseconds(*input).unwrap();
+            assert_eq!(*millis, E::decode_milliseconds_to_duration(&res).unwrap().num_milliseconds(), "{}", input);
+        }
+        for input in ["", "P", "PT", "P1DT", "1D", "PT1H1H", "PT1S1M", "P1.5D", "PT.5S", "PT1.S", "P1X", "+P1D"].iter() {
+            assert_eq!(ErrorKind::InvalidInput, E::encode_from_iso8601_duration_as_milliseconds(*input).err().unwrap().kind(), "{}", input);
+        }
+        let res = E::encode_from_iso8601_duration_as_milliseconds("P1Y");
+        assert_eq!("P1Y uses years or months which do not have a fixed length", res.err().unwrap().message());
+        assert!(E::encode_from_iso8601_duration_as_milliseconds("P2M").is_err());
+        assert_eq!(ErrorKind::OutOfRange, E::encode_from_iso8601_duration_as_milliseconds("P99999999999999W").err().unwrap().kind());
+    }
+
+    #[test]
+    fn duration_test() {
+        for_each_backend!(duration());
+    }
+
+    fn wide_integer<E: AttributeDecoder>() {
+        let res = E::encode_from_u128(u128::MAX).unwrap();
+        assert_eq!(BigInt::from(u128::MAX), offset_from_zero_center::<E>(&res));
+        assert_eq!(u128::MAX, E::decode_to_u128(&res).unwrap());
+        assert_eq!(ErrorKind::OutOfRange, E::decode_to_i128(&res).err().unwrap().kind());
+        let res = E::encode_from_i128(i128::MIN).unwrap();
+        assert_eq!(i128::MIN, E::decode_to_i128(&res).unwrap());
+        assert_eq!(ErrorKind::OutOfRange, E::decode_to_u128(&res).err().unwrap().kind());
+        assert_eq!(to_bigint::<E>(&E::encode_from_isize(-5isize).unwrap()), to_bigint::<E>(&E::encode_from_i128(-5i128).unwrap()));
+
+        let balance = BigInt::parse_bytes(b"-123456789012345678901234567890123456789012345678901234567890", 10).unwrap();
+        let res = E::encode_from_bigint(&balance).unwrap();
+        assert_eq!(balance, E::decode_to_bigint(&res).unwrap());
+        let err = E::encode_from_bigint(&-(BigInt::from(1) << E::MAX_NEGATIVE_BITS)).err().unwrap();
+        assert_eq!(ErrorKind::OutOfRange, err.kind());
+        assert!(err.message().ends_with(&format!("does not fit in {} bits", E::MAX_NEGATIVE_BITS)));
+        assert!(E::encode_from_bigint(&((BigInt::from(1) << E::MAX_POSITIVE_BITS) - 1)).is_ok());
+        assert_eq!(ErrorKind::OutOfRange, E::decode_to_bigint(&E::encoded_null().unwrap()).err().unwrap().kind());
+    }
+
+    #[test]
+    fn wide_integer_test() {
+        for_each_backend!(wide_integer());
+    }
+
+    fn fixed_width<E: AttributeDecoder>() {
+        assert_eq!(BigInt::from(-128), offset_from_zero_center::<E>(&E::encode_from_i8(i8::MIN).unwrap()));
+        assert_eq!(i8::MIN, E::decode_to_i8(&E::encode_from_i8(i8::MIN).unwrap()).unwrap());
+        assert_eq!(to_bigint::<E>(&E::encode_from_i64(-7).unwrap()), to_bigint::<E>(&E::encode_from_i8(-7).unwrap()));
+        assert_eq!(to_bigint::<E>(&E::encode_from_i64(-7).unwrap()), to_bigint::<E>(&E::encode_from_isize(-7isize).unwrap()));
+        assert_eq!(to_bigint::<E>(&E::encode_from_u64(usize::MAX as u64).unwrap()), to_bigint::<E>(&E::encode_from_usize(usize::MAX).unwrap()));
+        assert_eq!(u64::MAX, E::decode_to_u64(&E::encode_from_u64(u64::MAX).unwrap()).unwrap());
+        assert_eq!(i64::MIN, E::decode_to_i64(&E::encode_from_i64(i64::MIN).unwrap()).unwrap());
+
+        let res = E::encode_from_u16(256).unwrap();
+        assert_eq!(256u16, E::decode_to_u16(&res).unwrap());
+        assert_eq!(256i32, E::decode_to_i32(&res).unwrap());
+        assert_eq!(ErrorKind::OutOfRange, E::decode_to_u8(&res).err().unwrap().kind());
+        assert_eq!(ErrorKind::OutOfRange, E::decode_to_i8(&res).err().unwrap().kind());
+        assert_eq!(ErrorKind::OutOfRange, E::decode_to_u32(&E::encode_from_i32(-1).unwrap()).err().unwrap().kind());
+    }
+
+    #[test]
+    fn fixed_width_test() {
+        for_each_backend!(fixed_width());
+    }
+
+    /// Keeps the sign, exponent and top 20 mantissa bits of a float
+    struct LowPrecision<E>(PhantomData<E>);
+
+    impl<E: AttributeEncoder> AttributeEncoder for LowPrecision<E> {
+        type Output = E::Output;
+
+        const BITS_IN_ZERO: usize = E::BITS_IN_ZERO;
+        const MAX_POSITIVE_BITS: usize = E::MAX_POSITIVE_BITS;
+        const MAX_NEGATIVE_BITS: usize = E::MAX_NEGATIVE_BITS;
+        const FLOAT_PRECISION_BITS: usize = 31;
+
+        fn max() -> Self::Output {
+            E::max()
+        }
+
+        fn zero_center() -> Self::Output {
+            E::zero_center()
+        }
+
+        fn from_vec(bytes: Vec<u8>) -> Result<Self::Output, Error> {
+            E::from_vec(bytes)
+        }
+
+        fn try_add(lhs: Self::Output, rhs: Self::Output) -> Result<Self::Output, Error> {
+            E::try_add(lhs, rhs)
+        }
+
+        fn try_sub(lhs: Self::Output, rhs: Self::Output) -> Result<Self::Output, Error> {
+            E::try_sub(lhs, rhs)
+        }
+
+        fn try_neg(value: Self::Output) -> Result<Self::Output, Error> {
+            E::try_neg(value)
+        }
+    }
+
+    impl<E: AttributeDecoder> AttributeDecoder for LowPrecision<E> {
+        fn to_vec(value: &Self::Output) -> Vec<u8> {
+            E::to_vec(value)
+        }
+    }
+
+    fn float_precision<E: AttributeDecoder>() {
+        type L<E> = LowPrecision<E>;
+        let encode = |v: f64| to_bigint::<L<E>>(&L::<E>::encode_from_f64(v).unwrap());
+        let round_trip = |v: f64| L::<E>::decode_to_f64(&L::<E>::encode_from_f64(v).unwrap()).unwrap();
+
+        let one = L::<E>::encode_from_f64(1.0).unwrap();
+        assert_eq!(BigInt::from(1.0f64.to_bits() >> 32), offset_from_zero_center::<L<E>>(&one));
+        assert_eq!(encode(1.0), encode(1.0 + f64::EPSILON));
+        assert!(encode(1.0) < encode(1.001));
+        assert!(encode(-1.001) < encode(-1.0));
+        assert_eq!(1.5, round_trip(1.5));
+        let truncated = f64::from_bits(f64::MAX.to_bits() >> 32 << 32);
+        assert_eq!(truncated, round_trip(f64::MAX));
+        assert!(round_trip(f64::NAN).is_nan());
+        assert_eq!(f64::NEG_INFINITY, round_trip(f64::NEG_INFINITY));
+    }
+
+    #[test]
+    fn float_precision_test() {
+        for_each_backend!(float_precision());
+    }
+
+    fn f64_order<E: AttributeDecoder>(a: f64, b: f64) {
+        let expected = match (a.is_nan(), b.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => a.partial_cmp(&b).unwrap(),
+        };
+        let ea = to_bigint::<E>(&E::encode_from_f64(a).unwrap());
+        let eb = to_bigint::<E>(&E::encode_from_f64(b).unwrap());
+        assert_eq!(expected, ea.cmp(&eb));
+    }
+
+    fn f64_round_trip<E: AttributeDecoder>(a: f64) {
+        let res = E::decode_to_f64(&E::encode_from_f64(a).unwrap()).unwrap();
+        assert!(res == a || (a.is_nan() && res.is_nan()), "{} decoded as {}", a, res);
+    }
+
+    fn isize_round_trip<E: AttributeDecoder>(a: isize) {
+        assert_eq!(a, E::decode_to_isize(&E::encode_from_isize(a).unwrap()).unwrap());
+    }
+
+    fn timestamp_order<E: AttributeDecoder>(a: i64, b: i64) {
+        let to_encoded = |millis: i64| {
+            let dt = Utc.timestamp_millis_opt(millis).unwrap().to_rfc3339();
+            let seconds = to_bigint::<E>(&E::encode_from_rfc3339_as_unixtimestamp(dt.as_str()).unwrap());
+            let days = to_bigint::<E>(&E::encode_from_rfc3339_as_dayssince1900(dt.as_str()).unwrap());
+            let millis = to_bigint::<E>(&E::encode_from_rfc3339_as_unixtimestamp_millis(dt.as_str()).unwrap());
+            (seconds, days, millis)
+        };
+        let (sa, da, ma) = to_encoded(a);
+        let (sb, db, mb) = to_encoded(b);
+        assert_eq!(a.cmp(&b), ma.cmp(&mb));
+        assert!(a.cmp(&b) == sa.cmp(&sb) || sa == sb);
+        assert!(a.cmp(&b) == da.cmp(&db) || da == db);
+    }
+
+    fn i128_round_trip<E: AttributeDecoder>(a: i128) {
+        let res = E::encode_from_i128(a).unwrap();
+        assert_eq!(a, E::decode_to_i128(&res).unwrap());
+        assert_eq!(BigInt::from(a), E::decode_to_bigint(&res).unwrap());
+    }
+
+    proptest! {
+        #[test]
+        fn f64_order_test(a in any::<f64>(), b in any::<f64>()) {
+            for_each_backend!(f64_order(a, b));
+        }
+
+        #[test]
+        fn f64_round_trip_test(a in any::<f64>()) {
+            for_each_backend!(f64_round_trip(a));
+        }
+
+        #[test]
+        fn isize_round_trip_test(a in any::<isize>()) {
+            for_each_backend!(isize_round_trip(a));
+        }
+
+        #[test]
+        fn timestamp_order_test(a in -8_000_000_000_000i64..8_000_000_000_000, b in -8_000_000_000_000i64..8_000_000_000_000) {
+            for_each_backend!(timestamp_order(a, b));
+        }
+
+        #[test]
+        fn i128_round_trip_test(a in any::<i128>()) {
+            for_each_backend!(i128_round_trip(a));
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use super::{encode_offset, epoch_1900, to_bigint, AttributeDecoder, AttributeEncoder};
+use crate::errors::{Error, ErrorKind};
+
+use chrono::{DateTime, Months, NaiveDate, Utc};
+
+/// How an encoded attribute is compared to the threshold of a `Predicate`
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Comparison {
+    /// The attribute must be less than or equal to the threshold
+    LessThanOrEqual,
+    /// The attribute must be greater than or equal to the threshold
+    GreaterThanOrEqual,
+}
+
+/// A range proof request on an encoded attribute
+#[derive(Clone, Debug, PartialEq)]
+pub struct Predicate<T> {
+    /// How the attribute is compared to `threshold`
+    pub comparison: Comparison,
+    /// The encoded value to compare against
+    pub threshold: T,
+}
+
+impl<T> Predicate<T> {
+    /// Check whether `value` satisfies the predicate.
+    /// Provers use this to avoid creating proofs that will fail
+    pub fn is_satisfied_by<D: AttributeDecoder<Output = T> + ?Sized>(&self, value: &T) -> bool {
+        let value = to_bigint::<D>(value);
+        let threshold = to_bigint::<D>(&self.threshold);
+        match self.comparison {
+            Comparison::LessThanOrEqual => value <= threshold,
+            Comparison::GreaterThanOrEqual => value >= threshold,
+        }
+    }
+}
+
+/// The latest birthdate of someone who is at least `years` old on `as_of`.
+/// Someone born on February 29th turns a year older on March 1st in
+/// non-leap years, so for `as_of` on February 28th the result is February 28th
+pub fn latest_birthdate(as_of: NaiveDate, years: u32) -> Result<NaiveDate, Error> {
+    years
+        .checked_mul(12)
+        .and_then(|months| as_of.checked_sub_months(Months::new(months)))
+        .ok_or_else(|| Error::new(ErrorKind::OutOfRange, format!("{} years before {} is out of range", years, as_of)))
+}
+
+/// The predicate proving a birthdate encoded with `encode_from_rfc3339_as_dayssince1900`
+/// or `encode_from_iso8601_date_as_dayssince1900` belongs to someone who is at
+/// least `years` old on `as_of`
+pub fn at_least_age<E: AttributeEncoder + ?Sized>(as_of: NaiveDate, years: u32) -> Result<Predicate<E::Output>, Error> {
+    Ok(Predicate {
+        comparison: Comparison::LessThanOrEqual,
+        threshold: encode_days::<E>(latest_birthdate(as_of, years)?)?,
+    })
+}
+
+/// The predicate proving a birthdate encoded with `encode_from_rfc3339_as_dayssince1900`
+/// or `encode_from_iso8601_date_as_dayssince1900` belongs to someone who is
+/// younger than `years` on `as_of`
+pub fn under_age<E: AttributeEncoder + ?Sized>(as_of: NaiveDate, years: u32) -> Result<Predicate<E::Output>, Error> {
+    let latest = latest_birthdate(as_of, years)?;
+    let day_after = latest
+        .succ_opt()
+        .ok_or_else(|| Error::new(ErrorKind::OutOfRange, format!("The day after {} is out of range", latest)))?;
+    Ok(Predicate {
+        comparison: Comparison::GreaterThanOrEqual,
+        threshold: encode_days::<E>(day_after)?,
+    })
+}
+
+/// The predicate proving an expiry encoded with `encode_from_rfc3339_as_unixtimestamp`
+/// has not passed at `as_of`, i.e. it is at least one second later
+pub fn not_expired<E: AttributeEncoder + ?Sized>(as_of: DateTime<Utc>) -> Result<Predicate<E::Output>, Error> {
+    Ok(Predicate {
+        comparison: Comparison::GreaterThanOrEqual,
+        threshold: encode_offset::<E>(as_of.timestamp() + 1)?,
+    })
+}
+
+/// The predicate proving an expiry date encoded with `encode_from_rfc3339_as_dayssince1900`
+/// or `encode_from_iso8601_date_as_dayssince1900` has not passed on `as_of`.
+/// Credentials are valid through their expiry date
+pub fn not_expired_on<E: AttributeEncoder + ?Sized>(as_of: NaiveDate) -> Result<Predicate<E::Output>, Error> {
+    Ok(Predicate {
+        comparison: Comparison::GreaterThanOrEqual,
+        threshold: encode_days::<E>(as_of)?,
+    })
+}
+
+fn encode_days<E: AttributeEncoder + ?Sized>(date: NaiveDate) -> Result<E::Output, Error> {
+    encode_offset::<E>(date.signed_duration_since(epoch_1900()).num_days())
+}
+
+#[cfg(all(test, feature = "rsa-pure"))]
+mod tests {
+    use super::*;
+    use crate::encoding::rsa_pure::BigInteger;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn birthdate(value: &str) -> BigInteger {
+        BigInteger::encode_from_iso8601_date_as_dayssince1900(value).unwrap()
+    }
+
+    #[test]
+    fn age_test() {
+        let predicate = at_least_age::<BigInteger>(date(2021, 10, 16), 18).unwrap();
+        assert_eq!(Comparison::LessThanOrEqual, predicate.comparison);
+        assert_eq!(birthdate("2003-10-16"), predicate.threshold);
+        assert!(predicate.is_satisfied_by::<BigInteger>(&birthdate("2003-10-16")));
+        assert!(predicate.is_satisfied_by::<BigInteger>(&birthdate("1899-01-01")));
+        assert!(!predicate.is_satisfied_by::<BigInteger>(&birthdate("2003-10-17")));
+
+        let predicate = under_age::<BigInteger>(date(2021, 10, 16), 18).unwrap();
+        assert!(!predicate.is_satisfied_by::<BigInteger>(&birthdate("2003-10-16")));
+        assert!(predicate.is_satisfied_by::<BigInteger>(&birthdate("2003-10-17")));
+
+        // Leap day birthdays
+        assert_eq!(date(2004, 2, 28), latest_birthdate(date(2022, 2, 28), 18).unwrap());
+        assert_eq!(date(2004, 3, 1), latest_birthdate(date(2022, 3, 1), 18).unwrap());
+        assert_eq!(date(2003, 2, 28), latest_birthdate(date(2024, 2, 29), 21).unwrap());
+        let predicate = at_least_age::<BigInteger>(date(2022, 2, 28), 18).unwrap();
+        assert!(!predicate.is_satisfied_by::<BigInteger>(&birthdate("2004-02-29")));
+        let predicate = at_least_age::<BigInteger>(date(2022, 3, 1), 18).unwrap();
+        assert!(predicate.is_satisfied_by::<BigInteger>(&birthdate("2004-02-29")));
+
+        let birthdate = BigInteger::encode_from_rfc3339_as_dayssince1900("2003-10-16T23:00:00-00:00").unwrap();
+        assert!(at_least_age::<BigInteger>(date(2021, 10, 16), 18).unwrap().is_satisfied_by::<BigInteger>(&birthdate));
+        assert_eq!(ErrorKind::OutOfRange, latest_birthdate(date(2021, 10, 16), u32::MAX).unwrap_err().kind());
+    }
+
+    #[test]
+    fn expiry_test() {
+        let now = DateTime::parse_from_rfc3339("2021-10-16T12:00:00Z").unwrap().with_timezone(&Utc);
+        let predicate = not_expired::<BigInteger>(now).unwrap();
+        assert_eq!(Comparison::GreaterThanOrEqual, predicate.comparison);
+        let expiry = |value: &str| BigInteger::encode_from_rfc3339_as_unixtimestamp(value).unwrap();
+        assert!(predicate.is_satisfied_by::<BigInteger>(&expiry("2021-10-16T12:00:01Z")));
+        assert!(!predicate.is_satisfied_by::<BigInteger>(&expiry("2021-10-16T12:00:00Z")));
+        assert!(!predicate.is_satisfied_by::<BigInteger>(&expiry("1969-01-01T00:00:00Z")));
+
+        let predicate = not_expired_on::<BigInteger>(date(2021, 10, 16)).unwrap();
+        assert!(predicate.is_satisfied_by::<BigInteger>(&birthdate("2021-10-16")));
+        assert!(!predicate.is_satisfied_by::<BigInteger>(&birthdate("2021-10-15")));
+    }
+}
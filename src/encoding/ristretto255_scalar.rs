@@ -0,0 +1,110 @@
+use super::{AttributeDecoder, AttributeEncoder};
+use crate::errors::{Error, ErrorKind};
+
+use curve25519_dalek::scalar::Scalar;
+
+/// The number of bytes in a serialized scalar
+const SCALAR_SIZE: usize = 32;
+
+impl AttributeEncoder for Scalar {
+    type Output = Scalar;
+
+    /// The group order is just above 2^252 so zero is centered at 2^251
+    /// instead of 2^254
+    const BITS_IN_ZERO: usize = 251;
+    /// The group order is slightly more than 2^251 above `zero_center()`
+    const MAX_POSITIVE_BITS: usize = 251;
+
+    fn max() -> Self::Output {
+        Scalar::ZERO
+    }
+
+    fn zero_center() -> Self::Output {
+        let mut bytes = [0u8; SCALAR_SIZE];
+        bytes[Self::BITS_IN_ZERO / 8] = 1 << (Self::BITS_IN_ZERO % 8);
+        Scalar::from_bytes_mod_order(bytes)
+    }
+
+    fn from_vec(bytes: Vec<u8>) -> Result<Self::Output, Error> {
+        if bytes.len() > SCALAR_SIZE {
+            return Err(Error::new(ErrorKind::OutOfRange, format!("Expected at most {} bytes, found {}", SCALAR_SIZE, bytes.len())));
+        }
+        // Scalars are little endian
+        let mut data = [0u8; SCALAR_SIZE];
+        for (i, b) in bytes.iter().rev().enumerate() {
+            data[i] = *b;
+        }
+        Ok(Scalar::from_bytes_mod_order(data))
+    }
+}
+
+impl AttributeDecoder for Scalar {
+    fn to_vec(value: &Self::Output) -> Vec<u8> {
+        let mut bytes = value.to_bytes().to_vec();
+        bytes.reverse();
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc3339_string_convert() {
+        let res = Scalar::encode_from_rfc3339_as_unixtimestamp("2018-01-26T18:30:09.453+00:00");
+        assert!(res.is_ok());
+        assert_eq!(Scalar::from(1_516_991_409u64) + Scalar::zero_center(), res.unwrap());
+
+        let res = Scalar::encode_from_rfc3339_as_unixtimestamp("1970-01-01T00:00:00.000+00:00");
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap(), Scalar::zero_center());
+
+        let res = Scalar::encode_from_rfc3339_as_unixtimestamp("1900");
+        assert_eq!(ErrorKind::InvalidDate, res.unwrap_err().kind());
+
+        let res = Scalar::encode_from_rfc3339_as_dayssince1900("1982-12-20T10:45:00.000-06:00");
+        assert!(res.is_ok());
+        let res = res.unwrap();
+        assert_eq!(res, Scalar::zero_center() + Scalar::from(30303u64));
+        assert_eq!(chrono::NaiveDate::from_ymd_opt(1982, 12, 20).unwrap(), Scalar::decode_dayssince1900_to_date(&res).unwrap());
+    }
+
+    #[test]
+    fn decimal_test() {
+        let res1 = Scalar::encode_from_f64(1.33f32);
+        assert!(res1.is_ok());
+        let res2 = Scalar::encode_from_f64(-1.33f32);
+        assert!(res2.is_ok());
+        assert_eq!(Scalar::zero_center() + Scalar::zero_center(), res1.unwrap() + res2.unwrap());
+
+        let res1 = Scalar::encode_from_f64(f64::NEG_INFINITY);
+        assert_eq!(Scalar::from(8u64), res1.unwrap());
+        let res1 = Scalar::encode_from_f64(f64::INFINITY);
+        assert_eq!(-Scalar::from(9u64), res1.unwrap());
+        let res1 = Scalar::encode_from_f64(f64::NAN);
+        assert_eq!(-Scalar::from(8u64), res1.unwrap());
+    }
+
+    #[test]
+    fn fixed_point_test() {
+        let res = Scalar::encode_from_decimal_str("-85000.25", 2);
+        assert!(res.is_ok());
+        let res = res.unwrap();
+        assert_eq!(Scalar::zero_center() - Scalar::from(8_500_025u64), res);
+        assert_eq!("-85000.25", Scalar::decode_to_decimal(&res, 2).unwrap().to_string());
+
+        let res = Scalar::encode_from_decimal_str("1e74", 2);
+        assert_eq!(ErrorKind::OutOfRange, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn decode_test() {
+        assert_eq!(ErrorKind::OutOfRange, Scalar::from_vec(vec![1u8; 33]).unwrap_err().kind());
+        assert_eq!(Scalar::from(258u64), Scalar::from_vec(vec![1u8, 2u8]).unwrap());
+        assert_eq!(usize::MAX, Scalar::decode_to_usize(&Scalar::encode_from_usize(usize::MAX).unwrap()).unwrap());
+        assert!(Scalar::decode_to_usize(&Scalar::encode_from_isize(-1isize).unwrap()).is_err());
+        assert!(Scalar::is_encoded_null(&Scalar::encoded_null().unwrap()).unwrap());
+        assert!(!Scalar::is_encoded_null(&Scalar::zero_center()).unwrap());
+    }
+}
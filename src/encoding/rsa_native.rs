@@ -1,11 +1,18 @@
-use super::{AttributeEncoder, BITS_IN_ZERO};
+use super::{AttributeDecoder, AttributeEncoder, MIN_OFFSET_BITS};
+use crate::errors::{Error, ErrorKind};
 
+use bigdecimal::BigDecimal;
+use digest::{Digest, generic_array::typenum::U32};
+use num_bigint::BigInt;
 use openssl::{
-    bn::{BigNum, BigNumRef}
+    bn::{BigNum, BigNumContext, BigNumRef}
 };
 
 use std::{cmp::{Eq, PartialEq}, ops::{Add, Sub, Neg}};
 
+/// The most bytes `from_vec` accepts, the size of `max()`
+const BIGNUMBER_SIZE: usize = 32;
+
 /// A simple wrapper class for converting attributes to cryptographic integers
 /// represented in OpenSSL's BigNum library
 #[derive(Debug)]
@@ -13,8 +20,29 @@ pub struct BigNumber(pub BigNum);
 
 impl BigNumber {
     /// Convert a hex string to a BigNumber
-    pub fn from_hex(n: &str) -> Result<BigNumber, String> {
-        Ok(BigNumber(BigNum::from_hex_str(n).map_err(|_| format!("Unable to convert {} to a BigNumber", n))?))
+    pub fn from_hex(n: &str) -> Result<BigNumber, Error> {
+        Ok(BigNumber(BigNum::from_hex_str(n).map_err(|_| Error::new(ErrorKind::InvalidInput, format!("Unable to convert {} to a BigNumber", n)))?))
+    }
+
+    /// Compute `self + rhs` without panicking if OpenSSL fails
+    pub fn checked_add(&self, rhs: &BigNumber) -> Result<BigNumber, Error> {
+        let mut bn = BigNum::new()?;
+        BigNumRef::checked_add(&mut bn, &self.0, &rhs.0)?;
+        Ok(BigNumber(bn))
+    }
+
+    /// Compute `self - rhs` without panicking if OpenSSL fails
+    pub fn checked_sub(&self, rhs: &BigNumber) -> Result<BigNumber, Error> {
+        let mut bn = BigNum::new()?;
+        BigNumRef::checked_sub(&mut bn, &self.0, &rhs.0)?;
+        Ok(BigNumber(bn))
+    }
+
+    /// Compute `-self` without panicking if OpenSSL fails
+    pub fn checked_neg(&self) -> Result<BigNumber, Error> {
+        let mut bn = self.0.to_owned()?;
+        bn.set_negative(!self.0.is_negative());
+        Ok(BigNumber(bn))
     }
 }
 
@@ -22,19 +50,15 @@ impl Add for BigNumber {
     type Output = Self;
 
     fn add(self, rhs: Self::Output) -> Self::Output {
-        let mut bn = BigNum::new().unwrap();
-        BigNumRef::checked_add(&mut bn, &self.0, &rhs.0).unwrap();
-        BigNumber(bn)
+        self.checked_add(&rhs).unwrap()
     }
 }
 
-impl<'a, 'b> Add<&'b BigNumber> for &'a BigNumber {
+impl<'b> Add<&'b BigNumber> for &BigNumber {
     type Output = BigNumber;
 
     fn add(self, rhs: &'b BigNumber) -> BigNumber {
-        let mut bn = BigNum::new().unwrap();
-        BigNumRef::checked_add(&mut bn, &self.0, &rhs.0).unwrap();
-        BigNumber(bn)
+        self.checked_add(rhs).unwrap()
     }
 }
 
@@ -42,9 +66,7 @@ impl Neg for BigNumber {
     type Output = BigNumber;
 
     fn neg(self) -> Self::Output {
-        let mut bn = BigNum::from_slice(&self.0.to_vec()).unwrap();
-        bn.set_negative(if self.0.is_negative() { false } else { true });
-        BigNumber(bn)
+        self.checked_neg().unwrap()
     }
 }
 
@@ -52,19 +74,15 @@ impl Sub for BigNumber {
     type Output = Self;
 
     fn sub(self, rhs: Self::Output) -> Self::Output {
-        let mut bn = BigNum::new().unwrap();
-        BigNumRef::checked_sub(&mut bn, &self.0, &rhs.0).unwrap();
-        BigNumber(bn)
+        self.checked_sub(&rhs).unwrap()
     }
 }
 
-impl<'a, 'b> Sub<&'b BigNumber> for &'a BigNumber {
+impl<'b> Sub<&'b BigNumber> for &BigNumber {
     type Output = BigNumber;
 
     fn sub(self, rhs: &'b Self::Output) -> Self::Output {
-        let mut bn = BigNum::new().unwrap();
-        BigNumRef::checked_sub(&mut bn, &self.0, &rhs.0).unwrap();
-        BigNumber(bn)
+        self.checked_sub(rhs).unwrap()
     }
 }
 
@@ -89,19 +107,195 @@ impl Eq for BigNumber{}
 impl AttributeEncoder for BigNumber {
     type Output = BigNumber;
 
+    const BITS_IN_ZERO: usize = 254;
+    /// `max()` is 2^256 - 1 so positive values have more room than negative ones
+    const MAX_POSITIVE_BITS: usize = 255;
+
     fn max() -> Self::Output {
-        let bytes = vec![0xFF; 32];
+        let bytes = vec![0xFF; BIGNUMBER_SIZE];
         Self(BigNum::from_slice(bytes.as_slice()).unwrap())
     }
 
     fn zero_center() -> Self::Output {
         let mut bn = BigNum::new().unwrap();
-        bn.set_bit(BITS_IN_ZERO as i32).unwrap();
+        bn.set_bit(Self::BITS_IN_ZERO as i32).unwrap();
         Self(bn)
     }
 
-    fn from_vec(bytes: Vec<u8>) -> Self::Output {
-        Self(BigNum::from_slice(bytes.as_slice()).unwrap()) 
+    fn from_vec(bytes: Vec<u8>) -> Result<Self::Output, Error> {
+        if bytes.len() > BIGNUMBER_SIZE {
+            return Err(Error::new(ErrorKind::OutOfRange, format!("Expected at most {} bytes, found {}", BIGNUMBER_SIZE, bytes.len())));
+        }
+        Ok(Self(BigNum::from_slice(bytes.as_slice())?))
+    }
+
+    fn try_add(lhs: Self::Output, rhs: Self::Output) -> Result<Self::Output, Error> {
+        lhs.checked_add(&rhs)
+    }
+
+    fn try_sub(lhs: Self::Output, rhs: Self::Output) -> Result<Self::Output, Error> {
+        lhs.checked_sub(&rhs)
+    }
+
+    fn try_neg(value: Self::Output) -> Result<Self::Output, Error> {
+        value.checked_neg()
+    }
+}
+
+impl AttributeDecoder for BigNumber {
+    fn to_vec(value: &Self::Output) -> Vec<u8> {
+        value.0.to_vec()
+    }
+}
+
+/// The fewest bits a context may use so every i64, u64 and f64 can be encoded.
+/// A `bits`-bit modulus can be as small as 2^(bits - 1) and values are centered
+/// at 2^(bits - 2), so only 2^(bits - 2) - 10 values above the center are
+/// guaranteed to stay below the +infinity sentinel. Holding `u64::MAX`
+/// needs 2^(bits - 2) - 10 >= 2^64, i.e. three bits more than `MIN_OFFSET_BITS`
+pub const MIN_MESSAGE_BITS: usize = MIN_OFFSET_BITS + 3;
+
+/// Generates `EncodingContext` methods that encode with the `BigNumber`
+/// method of the same name and move the result into the context's range.
+/// Generic parameters go in brackets and must match the `AttributeEncoder` signature
+macro_rules! rebased_encoders {
+    ($(fn $name:ident[$($generics:tt)*]($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            #[doc = concat!("See `AttributeEncoder::", stringify!($name), "`")]
+            pub fn $name<$($generics)*>(&self, $($arg: $ty),*) -> Result<BigNumber, Error> {
+                self.rebase(BigNumber::$name($($arg),*)?)
+            }
+        )*
+    };
+}
+
+/// Encodes attributes relative to an RSA modulus or a CL signature message
+/// space bound instead of the fixed 256-bit range used by `BigNumber`.
+///
+/// Values are centered at 2^(bits - 2) where `bits` is the bit length of the bound,
+/// which matches `BigNumber::zero_center()` for a 256-bit message space.
+/// The NULL and -infinity sentinels keep their values and the +infinity
+/// and NaN sentinels stay the same distance below `max()`.
+/// Every encoded value is guaranteed to be in [0, `max()`].
+#[derive(Debug)]
+pub struct EncodingContext {
+    max: BigNumber,
+    zero_center: BigNumber,
+}
+
+impl EncodingContext {
+    /// Create a context for values that must be less than `modulus`
+    pub fn with_modulus(modulus: &BigNumber) -> Result<Self, Error> {
+        let bits = modulus.0.num_bits() as usize;
+        if modulus.0.is_negative() || bits < MIN_MESSAGE_BITS {
+            return Err(Error::new(ErrorKind::InvalidInput, format!("The modulus must be positive and at least {} bits", MIN_MESSAGE_BITS)));
+        }
+        Ok(Self {
+            max: modulus.checked_sub(&BigNumber::from(1u64))?,
+            zero_center: Self::center(bits)?,
+        })
+    }
+
+    /// Create a context for a message space of `bits` bits, i.e. values less than 2^`bits`
+    pub fn with_message_bits(bits: usize) -> Result<Self, Error> {
+        if bits < MIN_MESSAGE_BITS {
+            return Err(Error::new(ErrorKind::InvalidInput, format!("The message space must be at least {} bits", MIN_MESSAGE_BITS)));
+        }
+        let mut bound = BigNum::new()?;
+        bound.set_bit(bits as i32)?;
+        Ok(Self {
+            max: BigNumber(bound).checked_sub(&BigNumber::from(1u64))?,
+            zero_center: Self::center(bits)?,
+        })
+    }
+
+    fn center(bits: usize) -> Result<BigNumber, Error> {
+        let mut bn = BigNum::new()?;
+        bn.set_bit(bits as i32 - 2)?;
+        Ok(BigNumber(bn))
+    }
+
+    /// Return the highest value this context will produce
+    pub fn max(&self) -> Result<BigNumber, Error> {
+        Ok(BigNumber(self.max.0.to_owned()?))
+    }
+
+    /// Return the value that represents zero
+    pub fn zero_center(&self) -> Result<BigNumber, Error> {
+        Ok(BigNumber(self.zero_center.0.to_owned()?))
+    }
+
+    rebased_encoders! {
+        fn encoded_null[]();
+        fn encode_from_rfc3339_as_unixtimestamp['a, A: Into<&'a str>](value: A);
+        fn encode_from_rfc3339_as_unixtimestamp_millis['a, A: Into<&'a str>](value: A);
+        fn encode_from_rfc3339_as_dayssince1900['a, A: Into<&'a str>](value: A);
+        fn encode_from_iso8601_date_as_dayssince1900['a, A: Into<&'a str>](value: A);
+        fn encode_from_iso8601_time_as_milliseconds['a, A: Into<&'a str>](value: A);
+        fn encode_from_iso8601_duration_as_milliseconds['a, A: Into<&'a str>](value: A);
+        fn encode_from_bool[A: Into<bool>](value: A);
+        fn encode_from_enum[T: PartialEq](value: &T, variants: &[T]);
+        fn encode_from_f64[A: Into<f64>](value: A);
+        fn encode_from_decimal[](value: &BigDecimal, scale: u32);
+        fn encode_from_decimal_str['a, A: Into<&'a str>](value: A, scale: u32);
+        fn encode_from_isize[A: Into<isize>](value: A);
+        fn encode_from_usize[A: Into<usize>](value: A);
+        fn encode_from_i8[](value: i8);
+        fn encode_from_i16[](value: i16);
+        fn encode_from_i32[](value: i32);
+        fn encode_from_i64[](value: i64);
+        fn encode_from_u8[](value: u8);
+        fn encode_from_u16[](value: u16);
+        fn encode_from_u32[](value: u32);
+        fn encode_from_u64[](value: u64);
+        fn encode_from_i128[A: Into<i128>](value: A);
+        fn encode_from_u128[A: Into<u128>](value: A);
+        fn encode_from_bigint[](value: &BigInt);
+    }
+
+    /// See `AttributeEncoder::encode_from_utf8_as_hash`.
+    /// The hash is reduced into [9, `max()` - 10] so it never collides
+    /// with the NULL, -infinity, +infinity or NaN sentinels
+    pub fn encode_from_utf8_as_hash<'a, A: Into<&'a str>, D: Digest<OutputSize = U32> + Default>(&self, value: A) -> Result<BigNumber, Error> {
+        let hash = BigNumber::encode_from_utf8_as_hash::<A, D>(value)?;
+        let range = self.max.checked_sub(&BigNumber::from(18u64))?;
+        let mut bn = BigNum::new()?;
+        let mut ctx = BigNumContext::new()?;
+        bn.nnmod(&hash.0, &range.0, &mut ctx)?;
+        BigNumber(bn).checked_add(&BigNumber::from(9u64))
+    }
+
+    /// Convert a value encoded by this context to the equivalent `BigNumber`
+    /// encoding so it can be decoded with `AttributeDecoder`
+    pub fn to_default(&self, value: &BigNumber) -> Result<BigNumber, Error> {
+        let sentinel = BigNumber::from(8u64);
+        if value.0 > self.max.0 || value.0.is_negative() {
+            return Err(Error::new(ErrorKind::OutOfRange, "Value is outside the range of this context"));
+        }
+        if value.0 <= sentinel.0 {
+            BigNumber::from_vec(value.0.to_vec())
+        } else if value.0 >= self.max.checked_sub(&BigNumber::from(9u64))?.0 {
+            BigNumber::max().checked_sub(&self.max.checked_sub(value)?)
+        } else {
+            value.checked_sub(&self.zero_center)?.checked_add(&BigNumber::zero_center())
+        }
+    }
+
+    /// Move a value from the default `BigNumber` range to this context
+    fn rebase(&self, value: BigNumber) -> Result<BigNumber, Error> {
+        let sentinel = BigNumber::from(8u64);
+        if value.0 <= sentinel.0 {
+            return Ok(value);
+        }
+        let default_max = BigNumber::max();
+        if value.0 >= default_max.checked_sub(&BigNumber::from(9u64))?.0 {
+            return self.max.checked_sub(&default_max.checked_sub(&value)?);
+        }
+        let result = value.checked_sub(&BigNumber::zero_center())?.checked_add(&self.zero_center)?;
+        if result.0 <= sentinel.0 || result.0 >= self.max.checked_sub(&BigNumber::from(9u64))?.0 {
+            return Err(Error::new(ErrorKind::OutOfRange, "Value does not fit in the range of this context"));
+        }
+        Ok(result)
     }
 }
 
@@ -126,6 +320,100 @@ mod tests {
 
         let res = BigNumber::encode_from_rfc3339_as_unixtimestamp("1900");
         assert!(res.is_err());
+        assert_eq!(ErrorKind::InvalidDate, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn bool_enum_test() {
+        let res = BigNumber::encode_from_bool(false);
+        assert!(res.is_ok());
+        assert_eq!(BigNumber::zero_center(), res.unwrap());
+        let res = BigNumber::encode_from_bool(true);
+        assert!(res.is_ok());
+        let res = res.unwrap();
+        assert_eq!(BigNumber::zero_center() + BigNumber::from(1u64), res);
+        assert!(BigNumber::decode_to_bool(&res).unwrap());
+        assert!(BigNumber::decode_to_bool(&(BigNumber::zero_center() + BigNumber::from(2u64))).is_err());
+
+        let variants = ["passport", "drivers_license", "national_id"];
+        let res = BigNumber::encode_from_enum(&"national_id", &variants);
+        assert!(res.is_ok());
+        let res = res.unwrap();
+        assert_eq!(BigNumber::zero_center() + BigNumber::from(2u64), res);
+        assert_eq!(&"national_id", BigNumber::decode_to_enum(&res, &variants).unwrap());
+
+        let res = BigNumber::encode_from_enum(&"birth_certificate", &variants);
+        assert_eq!(ErrorKind::InvalidInput, res.unwrap_err().kind());
+        let res = BigNumber::encode_from_enum(&"passport", &["passport", "passport"]);
+        assert_eq!(ErrorKind::InvalidInput, res.unwrap_err().kind());
+        let res = BigNumber::decode_to_enum(&(BigNumber::zero_center() + BigNumber::from(3u64)), &variants);
+        assert_eq!(ErrorKind::OutOfRange, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn context_test() {
+        let default = EncodingContext::with_message_bits(256).unwrap();
+        assert_eq!(BigNumber::max(), default.max().unwrap());
+        assert_eq!(BigNumber::zero_center(), default.zero_center().unwrap());
+        assert_eq!(BigNumber::encode_from_isize(-42isize).unwrap(), default.encode_from_isize(-42isize).unwrap());
+        assert_eq!(BigNumber::encode_from_f64(f64::NAN).unwrap(), default.encode_from_f64(f64::NAN).unwrap());
+
+        let context = EncodingContext::with_message_bits(128).unwrap();
+        let res = context.encode_from_isize(isize::MIN).unwrap();
+        assert_eq!(isize::MIN, BigNumber::decode_to_isize(&context.to_default(&res).unwrap()).unwrap());
+        let res = context.encode_from_i64(i64::MIN).unwrap();
+        assert_eq!(i64::MIN, BigNumber::decode_to_i64(&context.to_default(&res).unwrap()).unwrap());
+        let res = context.encode_from_u8(u8::MAX).unwrap();
+        assert_eq!(u8::MAX, BigNumber::decode_to_u8(&context.to_default(&res).unwrap()).unwrap());
+        let res = context.encode_from_f64(f64::MAX).unwrap();
+        assert!(res.0 < context.max().unwrap().0);
+        assert_eq!(f64::MAX, BigNumber::decode_to_f64(&context.to_default(&res).unwrap()).unwrap());
+        let res = context.encode_from_f64(f64::INFINITY).unwrap();
+        assert_eq!(context.max().unwrap() - BigNumber::from(9u64), res);
+        assert_eq!(f64::INFINITY, BigNumber::decode_to_f64(&context.to_default(&res).unwrap()).unwrap());
+        let res = context.encoded_null().unwrap();
+        assert!(BigNumber::is_encoded_null(&context.to_default(&res).unwrap()).unwrap());
+
+        let res = context.encode_from_decimal_str("1e40", 2);
+        assert_eq!(ErrorKind::OutOfRange, res.unwrap_err().kind());
+
+        let modulus = BigNumber::from_hex("C7F1A9E5D3B2F10937E5A4D2C9B8F7E6D5C4B3A2918F7E6D5C4B3A2918F7E6D5").unwrap();
+        let context = EncodingContext::with_modulus(&modulus).unwrap();
+        let res = context.encode_from_utf8_as_hash::<_, sha2::Sha256>("first_name").unwrap();
+        assert!(res.0 < modulus.0);
+        let res = context.encode_from_f64(f64::NAN).unwrap();
+        assert_eq!(modulus - BigNumber::from(9u64), res);
+
+        let context = EncodingContext::with_message_bits(MIN_MESSAGE_BITS).unwrap();
+        let lowest = BigNumber::from(9u64);
+        let highest = context.max().unwrap() - BigNumber::from(10u64);
+        for i in 0..1000 {
+            let res = context.encode_from_utf8_as_hash::<_, sha2::Sha256>(i.to_string().as_str()).unwrap();
+            assert!(lowest.0 <= res.0 && res.0 <= highest.0);
+        }
+
+        assert_eq!(ErrorKind::InvalidInput, EncodingContext::with_message_bits(64).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidInput, EncodingContext::with_modulus(&BigNumber::from(1_000_000u64)).unwrap_err().kind());
+    }
+
+    #[test]
+    fn min_message_bits_test() {
+        let mut smallest = BigNum::new().unwrap();
+        smallest.set_bit(MIN_MESSAGE_BITS as i32 - 1).unwrap();
+        let smallest = BigNumber(smallest);
+        for context in [EncodingContext::with_modulus(&smallest).unwrap(), EncodingContext::with_message_bits(MIN_MESSAGE_BITS).unwrap()].iter() {
+            let res = context.encode_from_u64(u64::MAX).unwrap();
+            assert_eq!(u64::MAX, BigNumber::decode_to_u64(&context.to_default(&res).unwrap()).unwrap());
+            let res = context.encode_from_i64(i64::MIN).unwrap();
+            assert_eq!(i64::MIN, BigNumber::decode_to_i64(&context.to_default(&res).unwrap()).unwrap());
+            let res = context.encode_from_f64(f64::MIN).unwrap();
+            assert_eq!(f64::MIN, BigNumber::decode_to_f64(&context.to_default(&res).unwrap()).unwrap());
+            assert_eq!(ErrorKind::OutOfRange, context.encode_from_u128(u128::MAX).unwrap_err().kind());
+        }
+
+        let largest_too_small = smallest.checked_sub(&BigNumber::from(1u64)).unwrap();
+        assert_eq!(ErrorKind::InvalidInput, EncodingContext::with_modulus(&largest_too_small).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidInput, EncodingContext::with_message_bits(MIN_MESSAGE_BITS - 1).unwrap_err().kind());
     }
 
     #[test]
@@ -134,56 +422,109 @@ mod tests {
         assert!(res1.is_ok());
         let res2 = BigNumber::encode_from_f64(-1.33f32);
         assert!(res2.is_ok());
-        assert_eq!(BigNumber::zero_center(), res1.unwrap() + res2.unwrap());
+        assert_eq!(BigNumber::zero_center() + BigNumber::zero_center(), res1.unwrap() + res2.unwrap());
 
-        let res1 = BigNumber::encode_from_f64(std::f64::MAX);
+        let res1 = BigNumber::encode_from_f64(f64::MAX);
         assert!(res1.is_ok());
         let res2 = res1.unwrap();
         assert_eq!((&res2 - &res2).0, BigNum::new().unwrap());
 
-        let res3 = BigNumber::encode_from_f64(std::f64::MIN);
+        let res3 = BigNumber::encode_from_f64(f64::MIN);
         assert!(res3.is_ok());
-        assert_eq!(BigNumber::zero_center(), &res3.unwrap() + &res2);
+        assert_eq!(BigNumber::zero_center() + BigNumber::zero_center(), &res3.unwrap() + &res2);
 
-        let res1 = BigNumber::encode_from_f64(std::f64::NEG_INFINITY);
+        let res1 = BigNumber::encode_from_f64(f64::NEG_INFINITY);
         assert!(res1.is_ok());
         assert_eq!(BigNum::from_u32(8).unwrap(), res1.unwrap().0);
 
         let pos_inf = BigNumber::max() - BigNumber::from(9u64);
-        let res1 = BigNumber::encode_from_f64(std::f64::INFINITY);
+        let res1 = BigNumber::encode_from_f64(f64::INFINITY);
         assert!(res1.is_ok());
         assert_eq!(pos_inf, res1.unwrap());
 
-        let res1 = BigNumber::encode_from_f64(std::f64::NAN);
+        let res1 = BigNumber::encode_from_f64(f64::NAN);
         let nan = BigNumber::max() - BigNumber::from(8u64);
         assert!(res1.is_ok());
         assert_eq!(nan.0, res1.unwrap().0);
     }
 
     #[test]
-    fn size_test() {
+    fn fixed_point_test() {
+        use std::str::FromStr;
+
+        let res = BigNumber::encode_from_decimal_str("85000.25", 2);
+        assert!(res.is_ok());
+        let res = res.unwrap();
+        assert_eq!(BigNumber::zero_center() + BigNumber::from(8_500_025u64), res);
+        assert_eq!(BigDecimal::from_str("85000.25").unwrap(), BigNumber::decode_to_decimal(&res, 2).unwrap());
+
+        let res = BigNumber::encode_from_decimal_str("-0.10", 2);
+        assert!(res.is_ok());
+        let res = res.unwrap();
+        assert_eq!(BigNumber::zero_center() - BigNumber::from(10u64), res);
+        assert_eq!(BigDecimal::from_str("-0.1").unwrap(), BigNumber::decode_to_decimal(&res, 2).unwrap());
+
+        let res = BigNumber::encode_from_decimal(&BigDecimal::from_str("1.500").unwrap(), 1);
+        assert!(res.is_ok());
+        assert_eq!(BigNumber::zero_center() + BigNumber::from(15u64), res.unwrap());
+
+        let res = BigNumber::encode_from_decimal_str("1.005", 2);
+        assert_eq!(ErrorKind::InvalidInput, res.unwrap_err().kind());
+        let res = BigNumber::encode_from_decimal_str(format!("1.{}", "0".repeat(300)).as_str(), 2);
+        assert_eq!(BigNumber::zero_center() + BigNumber::from(100u64), res.unwrap());
+        let res = BigNumber::encode_from_decimal_str(format!("1.{}1", "0".repeat(300)).as_str(), 2);
+        assert_eq!(ErrorKind::InvalidInput, res.unwrap_err().kind());
+        let res = BigNumber::encode_from_decimal_str("salary", 2);
+        assert_eq!(ErrorKind::InvalidInput, res.unwrap_err().kind());
+        let res = BigNumber::encode_from_decimal_str("1e75", 2);
+        assert_eq!(ErrorKind::OutOfRange, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn decode_test() {
         let mut test_vectors = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         test_vectors.push("test_vectors");
         test_vectors.push("integers.txt");
-        let lines = std::fs::read_to_string(test_vectors).unwrap().split("\n").map(|s| s.to_string()).collect::<Vec<String>>();
-        assert_eq!(lines.len(), 7);
-        for i in 0..lines.len() - 1 {
-            let parts = lines[i].split(",").collect::<Vec<&str>>();
-            let value = parts[0].parse::<isize>().unwrap();
-            let expected = BigNumber::from_hex(parts[1]).unwrap();
-            let res = BigNumber::encode_from_isize(value);
-            assert!(res.is_ok());
-            assert_eq!(expected, res.unwrap());
+        let lines = std::fs::read_to_string(test_vectors).unwrap().split('\n').map(|s| s.to_string()).collect::<Vec<String>>();
+        for line in &lines[..lines.len() - 2] {
+            let parts = line.split(',').collect::<Vec<&str>>();
+            let value = parts[0].parse::<i64>().unwrap();
+            let res = BigNumber::decode_to_i64(&BigNumber::encode_from_i64(value).unwrap());
+            assert_eq!(value, res.unwrap());
         }
-        let parts = lines[lines.len() - 1].split(",").collect::<Vec<&str>>();
-        let value = parts[0].parse::<usize>().unwrap();
-        let expected = BigNumber::from_hex(parts[1]).unwrap();
-        let res = BigNumber::encode_from_usize(value);
-        assert!(res.is_ok());
-        assert_eq!(expected, res.unwrap());
+        let parts = lines[lines.len() - 2].split(',').collect::<Vec<&str>>();
+        let value = parts[0].parse::<u64>().unwrap();
+        let res = BigNumber::decode_to_u64(&BigNumber::encode_from_u64(value).unwrap());
+        assert_eq!(value, res.unwrap());
+        assert!(BigNumber::decode_to_usize(&BigNumber::encode_from_isize(-1isize).unwrap()).is_err());
+
+        assert_eq!(ErrorKind::OutOfRange, BigNumber::from_vec(vec![1u8; 33]).unwrap_err().kind());
+        assert_eq!(ErrorKind::OutOfRange, BigNumber::decode_to_isize(&BigNumber::encoded_null().unwrap()).unwrap_err().kind());
+        assert!(BigNumber::is_encoded_null(&BigNumber::encoded_null().unwrap()).unwrap());
+        assert!(!BigNumber::is_encoded_null(&BigNumber::zero_center()).unwrap());
+
+        let mut test_vectors = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_vectors.push("test_vectors");
+        test_vectors.push("floating.txt");
+        let contents = std::fs::read_to_string(test_vectors).unwrap();
+        for line in contents.lines() {
+            let parts = line.split(',').collect::<Vec<&str>>();
+            let value = match parts[0].trim() {
+                "subnormal" => f64::MIN_POSITIVE / 2.0,
+                v => v.parse::<f64>().unwrap()
+            };
+            let res = BigNumber::decode_to_f64(&BigNumber::encode_from_f64(value).unwrap()).unwrap();
+            if value.is_nan() {
+                assert!(res.is_nan());
+            } else {
+                assert_eq!(value, res);
+            }
+        }
+
+        let res = BigNumber::encode_from_rfc3339_as_unixtimestamp("2018-01-26T18:30:09.000+00:00").unwrap();
+        assert_eq!("2018-01-26T18:30:09+00:00", BigNumber::decode_unixtimestamp_to_datetime(&res).unwrap().to_rfc3339());
 
-        let parts = lines[lines.len() - 1].split(",").collect::<Vec<&str>>();
-        assert_eq!(parts[0], "null");
-        assert_eq!(BigNumber::from_hex(parts[1]).unwrap(), BigNumber::encoded_null().unwrap());
+        let res = BigNumber::encode_from_rfc3339_as_dayssince1900("1982-12-20T10:45:00.000-06:00").unwrap();
+        assert_eq!(chrono::NaiveDate::from_ymd_opt(1982, 12, 20).unwrap(), BigNumber::decode_dayssince1900_to_date(&res).unwrap());
     }
 }
\ No newline at end of file
@@ -0,0 +1,113 @@
+use super::{AttributeDecoder, AttributeEncoder};
+use crate::errors::{Error, ErrorKind};
+
+use num_bigint::{BigInt, Sign};
+
+use std::ops::{Add, Sub, Neg};
+
+/// The most bytes `from_vec` accepts, the size of `max()`
+const BIGINTEGER_SIZE: usize = 32;
+
+/// A simple wrapper class for converting attributes to cryptographic integers
+/// represented in the pure Rust num-bigint library
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct BigInteger(pub BigInt);
+
+impl Add for BigInteger {
+    type Output = Self;
+
+    fn add(self, rhs: Self::Output) -> Self::Output {
+        BigInteger(self.0 + rhs.0)
+    }
+}
+
+impl<'b> Add<&'b BigInteger> for &BigInteger {
+    type Output = BigInteger;
+
+    fn add(self, rhs: &'b BigInteger) -> BigInteger {
+        BigInteger(&self.0 + &rhs.0)
+    }
+}
+
+impl Neg for BigInteger {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        BigInteger(-self.0)
+    }
+}
+
+impl Sub for BigInteger {
+    type Output = Self;
+
+    fn sub(self, rhs: Self::Output) -> Self::Output {
+        BigInteger(self.0 - rhs.0)
+    }
+}
+
+impl<'b> Sub<&'b BigInteger> for &BigInteger {
+    type Output = BigInteger;
+
+    fn sub(self, rhs: &'b Self::Output) -> Self::Output {
+        BigInteger(&self.0 - &rhs.0)
+    }
+}
+
+impl From<u64> for BigInteger {
+    fn from(v: u64) -> Self {
+        BigInteger(BigInt::from(v))
+    }
+}
+
+impl From<i64> for BigInteger {
+    fn from(v: i64) -> Self { BigInteger(BigInt::from(v)) }
+}
+
+impl AttributeEncoder for BigInteger {
+    type Output = BigInteger;
+
+    const BITS_IN_ZERO: usize = 254;
+    /// `max()` is 2^256 - 1 so positive values have more room than negative ones
+    const MAX_POSITIVE_BITS: usize = 255;
+
+    fn max() -> Self::Output {
+        Self(BigInt::from_bytes_be(Sign::Plus, &[0xFF; BIGINTEGER_SIZE]))
+    }
+
+    fn zero_center() -> Self::Output {
+        Self(BigInt::from(1u64) << Self::BITS_IN_ZERO)
+    }
+
+    fn from_vec(bytes: Vec<u8>) -> Result<Self::Output, Error> {
+        if bytes.len() > BIGINTEGER_SIZE {
+            return Err(Error::new(ErrorKind::OutOfRange, format!("Expected at most {} bytes, found {}", BIGINTEGER_SIZE, bytes.len())));
+        }
+        Ok(Self(BigInt::from_bytes_be(Sign::Plus, bytes.as_slice())))
+    }
+}
+
+impl AttributeDecoder for BigInteger {
+    fn to_vec(value: &Self::Output) -> Vec<u8> {
+        value.0.to_bytes_be().1
+    }
+}
+
+#[cfg(all(test, feature = "rsa-native"))]
+mod tests {
+    use super::*;
+    use crate::encoding::rsa_native::BigNumber;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn rsa_native_equivalence_test(i in any::<isize>(), f in any::<f64>(), d in any::<i64>()) {
+            let expected = BigNumber::to_vec(&BigNumber::encode_from_isize(i).unwrap());
+            prop_assert_eq!(expected, BigInteger::to_vec(&BigInteger::encode_from_isize(i).unwrap()));
+            let expected = BigNumber::to_vec(&BigNumber::encode_from_f64(f).unwrap());
+            prop_assert_eq!(expected, BigInteger::to_vec(&BigInteger::encode_from_f64(f).unwrap()));
+            let decimal = bigdecimal::BigDecimal::new(d.into(), 2);
+            let expected = BigNumber::to_vec(&BigNumber::encode_from_decimal(&decimal, 2).unwrap());
+            prop_assert_eq!(expected, BigInteger::to_vec(&BigInteger::encode_from_decimal(&decimal, 2).unwrap()));
+        }
+    }
+}
@@ -0,0 +1,4 @@
+use super::{AttributeDecoder, AttributeEncoder};
+use crate::errors::{Error, ErrorKind};
+
+elliptic_curve_encoder!(p256);
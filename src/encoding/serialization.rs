@@ -0,0 +1,168 @@
+use super::AttributeDecoder;
+use crate::errors::{Error, ErrorKind};
+
+use num_bigint::BigUint;
+
+/// The string forms an encoded value can be serialized as.
+/// Every form uses the minimal big-endian representation of the integer
+/// so the same value produces the same string on every backend
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ValueFormat {
+    /// Lowercase hexadecimal
+    Hex,
+    /// Base 10 digits, as used by the `encoded` field of Indy credentials
+    Decimal,
+    /// URL safe base64 without padding
+    Base64Url,
+}
+
+/// Convert an encoded value to a string in `format`
+pub fn to_string<T: AttributeDecoder<Output = T>>(value: &T, format: ValueFormat) -> String {
+    let n = BigUint::from_bytes_be(T::to_vec(value).as_slice());
+    match format {
+        ValueFormat::Hex => n.to_str_radix(16),
+        ValueFormat::Decimal => n.to_str_radix(10),
+        ValueFormat::Base64Url => base64::encode_config(n.to_bytes_be(), base64::URL_SAFE_NO_PAD),
+    }
+}
+
+/// Parse a string created by `to_string`.
+/// Fails if `value` is not valid for `format` or is not a canonical value for `T`,
+/// i.e. it is larger than the group order, or if it is not exactly the string
+/// `to_string` produces, i.e. it has leading zeros or uppercase hex digits,
+/// so each value has only one accepted string
+pub fn from_str<T: AttributeDecoder<Output = T>>(value: &str, format: ValueFormat) -> Result<T, Error> {
+    let invalid = || Error::new(ErrorKind::InvalidInput, format!("'{}' is not a valid {:?} encoded value", value, format));
+    let n = match format {
+        ValueFormat::Hex if !value.is_empty() && value.bytes().all(|b| b.is_ascii_hexdigit()) => BigUint::parse_bytes(value.as_bytes(), 16),
+        ValueFormat::Decimal if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => BigUint::parse_bytes(value.as_bytes(), 10),
+        ValueFormat::Base64Url => base64::decode_config(value, base64::URL_SAFE_NO_PAD).ok().filter(|b| !b.is_empty()).map(|b| BigUint::from_bytes_be(b.as_slice())),
+        _ => None,
+    }.ok_or_else(invalid)?;
+    let result = T::from_vec(n.to_bytes_be())?;
+    if BigUint::from_bytes_be(T::to_vec(&result).as_slice()) != n {
+        return Err(Error::new(ErrorKind::OutOfRange, format!("'{}' is not a canonical encoded value", value)));
+    }
+    if to_string(&result, format) != value {
+        return Err(Error::new(ErrorKind::InvalidInput, format!("'{}' is not the canonical {:?} form", value, format)));
+    }
+    Ok(result)
+}
+
+/// Generates a module usable with `#[serde(with = "...")]` for one `ValueFormat`.
+/// The modules are only available with the `serde` feature
+macro_rules! serde_format {
+    ($(#[$doc:meta])* $name:ident, $format:expr) => {
+        $(#[$doc])*
+        #[cfg(feature = "serde")]
+        pub mod $name {
+            use super::{from_str, to_string, ValueFormat};
+            use crate::encoding::AttributeDecoder;
+            use serde::{de::Error as DeError, Deserialize, Deserializer, Serializer};
+
+            /// Serialize `value` as a string
+            pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+                where T: AttributeDecoder<Output = T>,
+                      S: Serializer {
+                serializer.serialize_str(to_string(value, $format).as_str())
+            }
+
+            /// Deserialize a value from a string
+            pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+                where T: AttributeDecoder<Output = T>,
+                      D: Deserializer<'de> {
+                let value = String::deserialize(deserializer)?;
+                from_str(value.as_str(), $format).map_err(DeError::custom)
+            }
+        }
+    };
+}
+
+serde_format!(
+    /// Serialize encoded values as lowercase hexadecimal strings.
+    /// Use with `#[serde(with = "aries_credx_framework_rs::encoding::serialization::hex")]`
+    hex, ValueFormat::Hex);
+
+serde_format!(
+    /// Serialize encoded values as base 10 strings like the Indy `encoded` field.
+    /// Use with `#[serde(with = "aries_credx_framework_rs::encoding::serialization::decimal")]`
+    decimal, ValueFormat::Decimal);
+
+serde_format!(
+    /// Serialize encoded values as unpadded URL safe base64 strings.
+    /// Use with `#[serde(with = "aries_credx_framework_rs::encoding::serialization::base64url")]`
+    base64url, ValueFormat::Base64Url);
+
+#[cfg(all(test, any(feature = "bls381", feature = "rsa-pure")))]
+mod tests {
+    use super::*;
+    use crate::encoding::AttributeEncoder;
+
+    #[cfg(feature = "rsa-pure")]
+    #[test]
+    fn format_test() {
+        use crate::encoding::rsa_pure::BigInteger;
+
+        let value = BigInteger::encoded_null().unwrap();
+        assert_eq!("7", to_string(&value, ValueFormat::Hex));
+        assert_eq!("7", to_string(&value, ValueFormat::Decimal));
+        assert_eq!("Bw", to_string(&value, ValueFormat::Base64Url));
+
+        let value = BigInteger::encode_from_isize(1isize).unwrap();
+        assert_eq!("4000000000000000000000000000000000000000000000000000000000000001", to_string(&value, ValueFormat::Hex));
+        assert_eq!("28948022309329048855892746252171976963317496166410141009864396001978282409985", to_string(&value, ValueFormat::Decimal));
+        assert_eq!("QAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAE", to_string(&value, ValueFormat::Base64Url));
+
+        for format in [ValueFormat::Hex, ValueFormat::Decimal, ValueFormat::Base64Url].iter() {
+            let s = to_string(&value, *format);
+            assert_eq!(value, from_str::<BigInteger>(s.as_str(), *format).unwrap());
+            assert_eq!(ErrorKind::InvalidInput, from_str::<BigInteger>("", *format).unwrap_err().kind());
+            assert_eq!(ErrorKind::InvalidInput, from_str::<BigInteger>("-1", *format).unwrap_err().kind());
+        }
+        assert_eq!(ErrorKind::InvalidInput, from_str::<BigInteger>("0x10", ValueFormat::Hex).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidInput, from_str::<BigInteger>("1.5", ValueFormat::Decimal).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidInput, from_str::<BigInteger>("B+w", ValueFormat::Base64Url).unwrap_err().kind());
+
+        for (s, format) in [("07", ValueFormat::Hex), ("7A", ValueFormat::Hex), ("007", ValueFormat::Decimal), ("AAc", ValueFormat::Base64Url)].iter() {
+            assert_eq!(ErrorKind::InvalidInput, from_str::<BigInteger>(s, *format).unwrap_err().kind(), "{}", s);
+        }
+    }
+
+    #[cfg(all(feature = "rsa-pure", feature = "serde"))]
+    #[test]
+    fn serde_test() {
+        use crate::encoding::rsa_pure::BigInteger;
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Attributes {
+            #[serde(with = "hex")]
+            name: BigInteger,
+            #[serde(with = "decimal")]
+            age: BigInteger,
+            #[serde(with = "base64url")]
+            height: BigInteger,
+        }
+
+        let attributes = Attributes {
+            name: BigInteger::encode_from_utf8_as_hash::<_, sha2::Sha256>("Alice").unwrap(),
+            age: BigInteger::encode_from_isize(30isize).unwrap(),
+            height: BigInteger::encode_from_f64(1.8).unwrap(),
+        };
+        let json = serde_json::to_string(&attributes).unwrap();
+        assert!(json.starts_with(r#"{"name":"3bc51062973c458d5a6f2d8d64a023246354ad7e064b1e4e009ec8a0699a3043","age":"28948022309329048855892746252171976963317496166410141009864396001978282410014","height":""#));
+        assert_eq!(attributes, serde_json::from_str(json.as_str()).unwrap());
+        assert!(serde_json::from_str::<Attributes>(r#"{"name":"zz","age":"1","height":"Bw"}"#).is_err());
+    }
+
+    #[cfg(feature = "bls381")]
+    #[test]
+    fn canonical_test() {
+        use amcl_wrapper::field_elem::FieldElement;
+
+        let value = FieldElement::encode_from_isize(-1isize).unwrap();
+        assert_eq!(value, from_str::<FieldElement>(to_string(&value, ValueFormat::Decimal).as_str(), ValueFormat::Decimal).unwrap());
+        let order = "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001";
+        assert_eq!(ErrorKind::OutOfRange, from_str::<FieldElement>(order, ValueFormat::Hex).unwrap_err().kind());
+    }
+}
@@ -0,0 +1,70 @@
+use std::fmt;
+
+/// The kinds of errors that can occur when encoding or decoding attributes
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ErrorKind {
+    /// The input is not a valid date
+    InvalidDate,
+    /// The input is not valid for the requested conversion
+    InvalidInput,
+    /// The value does not fit in the range supported by the encoding
+    OutOfRange,
+    /// The cryptographic backend failed, i.e. an allocation failure
+    Backend,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::InvalidDate => write!(f, "invalid date"),
+            ErrorKind::InvalidInput => write!(f, "invalid input"),
+            ErrorKind::OutOfRange => write!(f, "value out of range"),
+            ErrorKind::Backend => write!(f, "backend failure"),
+        }
+    }
+}
+
+/// The error type returned by the credential exchange framework
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Error {
+    kind: ErrorKind,
+    message: String,
+}
+
+impl Error {
+    /// Create a new error of `kind` with a descriptive `message`
+    pub fn new<M: Into<String>>(kind: ErrorKind, message: M) -> Self {
+        Self { kind, message: message.into() }
+    }
+
+    /// The kind of error that occurred
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// The descriptive message for this error
+    pub fn message(&self) -> &str {
+        self.message.as_str()
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.kind, self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<chrono::ParseError> for Error {
+    fn from(e: chrono::ParseError) -> Self {
+        Error::new(ErrorKind::InvalidDate, e.to_string())
+    }
+}
+
+#[cfg(feature = "rsa-native")]
+impl From<openssl::error::ErrorStack> for Error {
+    fn from(e: openssl::error::ErrorStack) -> Self {
+        Error::new(ErrorKind::Backend, e.to_string())
+    }
+}
@@ -0,0 +1,248 @@
+use crate::encoding::{hash::HashAlgorithm, AttributeDecoder};
+use crate::errors::{Error, ErrorKind};
+
+use ffi_support::{ByteBuffer, ExternError, FfiStr};
+
+/// Free a `ByteBuffer` returned by any of the encoding functions
+#[no_mangle]
+pub extern "C" fn aries_credx_bytebuffer_free(buffer: ByteBuffer) {
+    ffi_support::abort_on_panic::with_abort_on_panic(|| buffer.destroy())
+}
+
+fn as_str<'a>(value: &FfiStr<'a>) -> Result<&'a str, Error> {
+    value.as_opt_str().ok_or_else(|| Error::new(ErrorKind::InvalidInput, "Expected a non-null UTF-8 string"))
+}
+
+fn to_byte_buffer<E: AttributeDecoder>(value: E::Output) -> ByteBuffer {
+    ByteBuffer::from_vec(E::to_vec(&value))
+}
+
+fn encode_from_isize<E: AttributeDecoder>(value: i64) -> Result<ByteBuffer, Error> {
+    Ok(to_byte_buffer::<E>(E::encode_from_i64(value)?))
+}
+
+fn encode_from_usize<E: AttributeDecoder>(value: u64) -> Result<ByteBuffer, Error> {
+    Ok(to_byte_buffer::<E>(E::encode_from_u64(value)?))
+}
+
+fn encode_from_f64<E: AttributeDecoder>(value: f64) -> Result<ByteBuffer, Error> {
+    Ok(to_byte_buffer::<E>(E::encode_from_f64(value)?))
+}
+
+fn encode_from_rfc3339_as_unixtimestamp<E: AttributeDecoder>(value: FfiStr<'_>) -> Result<ByteBuffer, Error> {
+    Ok(to_byte_buffer::<E>(E::encode_from_rfc3339_as_unixtimestamp(as_str(&value)?)?))
+}
+
+fn encode_from_rfc3339_as_dayssince1900<E: AttributeDecoder>(value: FfiStr<'_>) -> Result<ByteBuffer, Error> {
+    Ok(to_byte_buffer::<E>(E::encode_from_rfc3339_as_dayssince1900(as_str(&value)?)?))
+}
+
+fn encode_from_utf8_as_hash<E: AttributeDecoder>(value: FfiStr<'_>) -> Result<ByteBuffer, Error> {
+    Ok(to_byte_buffer::<E>(E::encode_from_utf8_as_hash::<_, sha2::Sha256>(as_str(&value)?)?))
+}
+
+fn encode_from_utf8_as_hash_with<E: AttributeDecoder>(value: FfiStr<'_>, algorithm: FfiStr<'_>) -> Result<ByteBuffer, Error> {
+    let algorithm = as_str(&algorithm)?.parse::<HashAlgorithm>()?;
+    Ok(to_byte_buffer::<E>(algorithm.encode::<E>(as_str(&value)?)?))
+}
+
+fn encoded_null<E: AttributeDecoder>() -> Result<ByteBuffer, Error> {
+    Ok(to_byte_buffer::<E>(E::encoded_null()?))
+}
+
+/// Generates the C functions for a single `AttributeEncoder` backend.
+/// Every function returns the big-endian bytes of the encoded integer
+/// which must be freed with `aries_credx_bytebuffer_free`.
+/// On failure, `err` is populated and an empty buffer is returned.
+macro_rules! ffi_encoder {
+    ($encoder:ty,
+     $isize:ident,
+     $usize:ident,
+     $f64:ident,
+     $unixtimestamp:ident,
+     $dayssince1900:ident,
+     $hash:ident,
+     $hash_with:ident,
+     $null:ident) => {
+        /// Encode a signed integer.
+        /// The result is the same on 32-bit and 64-bit targets.
+        #[no_mangle]
+        pub extern "C" fn $isize(value: i64, err: &mut ExternError) -> ByteBuffer {
+            ffi_support::call_with_result(err, || encode_from_isize::<$encoder>(value))
+        }
+
+        /// Encode an unsigned integer.
+        /// The result is the same on 32-bit and 64-bit targets.
+        #[no_mangle]
+        pub extern "C" fn $usize(value: u64, err: &mut ExternError) -> ByteBuffer {
+            ffi_support::call_with_result(err, || encode_from_usize::<$encoder>(value))
+        }
+
+        /// Encode a 64-bit floating point number.
+        #[no_mangle]
+        pub extern "C" fn $f64(value: f64, err: &mut ExternError) -> ByteBuffer {
+            ffi_support::call_with_result(err, || encode_from_f64::<$encoder>(value))
+        }
+
+        /// Encode an RFC3339 date string as seconds since the unix epoch.
+        #[no_mangle]
+        pub extern "C" fn $unixtimestamp(value: FfiStr<'_>, err: &mut ExternError) -> ByteBuffer {
+            ffi_support::call_with_result(err, || encode_from_rfc3339_as_unixtimestamp::<$encoder>(value))
+        }
+
+        /// Encode an RFC3339 date string as days since 1900-01-01.
+        #[no_mangle]
+        pub extern "C" fn $dayssince1900(value: FfiStr<'_>, err: &mut ExternError) -> ByteBuffer {
+            ffi_support::call_with_result(err, || encode_from_rfc3339_as_dayssince1900::<$encoder>(value))
+        }
+
+        /// Encode a UTF-8 string as its SHA-256 hash.
+        #[no_mangle]
+        pub extern "C" fn $hash(value: FfiStr<'_>, err: &mut ExternError) -> ByteBuffer {
+            ffi_support::call_with_result(err, || encode_from_utf8_as_hash::<$encoder>(value))
+        }
+
+        /// Encode a UTF-8 string as its hash with the algorithm named by the
+        /// identifier `algorithm`, e.g. "sha3-256".
+        #[no_mangle]
+        pub extern "C" fn $hash_with(value: FfiStr<'_>, algorithm: FfiStr<'_>, err: &mut ExternError) -> ByteBuffer {
+            ffi_support::call_with_result(err, || encode_from_utf8_as_hash_with::<$encoder>(value, algorithm))
+        }
+
+        /// Return the value used to represent NULL.
+        #[no_mangle]
+        pub extern "C" fn $null(err: &mut ExternError) -> ByteBuffer {
+            ffi_support::call_with_result(err, encoded_null::<$encoder>)
+        }
+    };
+}
+
+#[cfg(feature = "bls377")]
+ffi_encoder!(ark_bls12_377::Fr,
+             aries_credx_bls377_encode_from_isize,
+             aries_credx_bls377_encode_from_usize,
+             aries_credx_bls377_encode_from_f64,
+             aries_credx_bls377_encode_from_rfc3339_as_unixtimestamp,
+             aries_credx_bls377_encode_from_rfc3339_as_dayssince1900,
+             aries_credx_bls377_encode_from_utf8_as_hash,
+             aries_credx_bls377_encode_from_utf8_as_hash_with,
+             aries_credx_bls377_encoded_null);
+
+#[cfg(feature = "bls381")]
+ffi_encoder!(amcl_wrapper::field_elem::FieldElement,
+             aries_credx_bls381_encode_from_isize,
+             aries_credx_bls381_encode_from_usize,
+             aries_credx_bls381_encode_from_f64,
+             aries_credx_bls381_encode_from_rfc3339_as_unixtimestamp,
+             aries_credx_bls381_encode_from_rfc3339_as_dayssince1900,
+             aries_credx_bls381_encode_from_utf8_as_hash,
+             aries_credx_bls381_encode_from_utf8_as_hash_with,
+             aries_credx_bls381_encoded_null);
+
+#[cfg(feature = "bn254")]
+ffi_encoder!(ark_bn254::Fr,
+             aries_credx_bn254_encode_from_isize,
+             aries_credx_bn254_encode_from_usize,
+             aries_credx_bn254_encode_from_f64,
+             aries_credx_bn254_encode_from_rfc3339_as_unixtimestamp,
+             aries_credx_bn254_encode_from_rfc3339_as_dayssince1900,
+             aries_credx_bn254_encode_from_utf8_as_hash,
+             aries_credx_bn254_encode_from_utf8_as_hash_with,
+             aries_credx_bn254_encoded_null);
+
+#[cfg(feature = "ristretto255")]
+ffi_encoder!(curve25519_dalek::scalar::Scalar,
+             aries_credx_ristretto255_encode_from_isize,
+             aries_credx_ristretto255_encode_from_usize,
+             aries_credx_ristretto255_encode_from_f64,
+             aries_credx_ristretto255_encode_from_rfc3339_as_unixtimestamp,
+             aries_credx_ristretto255_encode_from_rfc3339_as_dayssince1900,
+             aries_credx_ristretto255_encode_from_utf8_as_hash,
+             aries_credx_ristretto255_encode_from_utf8_as_hash_with,
+             aries_credx_ristretto255_encoded_null);
+
+#[cfg(feature = "rsa-native")]
+ffi_encoder!(crate::encoding::rsa_native::BigNumber,
+             aries_credx_rsa_native_encode_from_isize,
+             aries_credx_rsa_native_encode_from_usize,
+             aries_credx_rsa_native_encode_from_f64,
+             aries_credx_rsa_native_encode_from_rfc3339_as_unixtimestamp,
+             aries_credx_rsa_native_encode_from_rfc3339_as_dayssince1900,
+             aries_credx_rsa_native_encode_from_utf8_as_hash,
+             aries_credx_rsa_native_encode_from_utf8_as_hash_with,
+             aries_credx_rsa_native_encoded_null);
+
+#[cfg(feature = "rsa-pure")]
+ffi_encoder!(crate::encoding::rsa_pure::BigInteger,
+             aries_credx_rsa_pure_encode_from_isize,
+             aries_credx_rsa_pure_encode_from_usize,
+             aries_credx_rsa_pure_encode_from_f64,
+             aries_credx_rsa_pure_encode_from_rfc3339_as_unixtimestamp,
+             aries_credx_rsa_pure_encode_from_rfc3339_as_dayssince1900,
+             aries_credx_rsa_pure_encode_from_utf8_as_hash,
+             aries_credx_rsa_pure_encode_from_utf8_as_hash_with,
+             aries_credx_rsa_pure_encoded_null);
+
+#[cfg(feature = "secp256k1")]
+ffi_encoder!(k256::Scalar,
+             aries_credx_secp256k1_encode_from_isize,
+             aries_credx_secp256k1_encode_from_usize,
+             aries_credx_secp256k1_encode_from_f64,
+             aries_credx_secp256k1_encode_from_rfc3339_as_unixtimestamp,
+             aries_credx_secp256k1_encode_from_rfc3339_as_dayssince1900,
+             aries_credx_secp256k1_encode_from_utf8_as_hash,
+             aries_credx_secp256k1_encode_from_utf8_as_hash_with,
+             aries_credx_secp256k1_encoded_null);
+
+#[cfg(feature = "secp256r1")]
+ffi_encoder!(p256::Scalar,
+             aries_credx_secp256r1_encode_from_isize,
+             aries_credx_secp256r1_encode_from_usize,
+             aries_credx_secp256r1_encode_from_f64,
+             aries_credx_secp256r1_encode_from_rfc3339_as_unixtimestamp,
+             aries_credx_secp256r1_encode_from_rfc3339_as_dayssince1900,
+             aries_credx_secp256r1_encode_from_utf8_as_hash,
+             aries_credx_secp256r1_encode_from_utf8_as_hash_with,
+             aries_credx_secp256r1_encoded_null);
+
+#[cfg(all(test, feature = "bls381"))]
+mod tests {
+    use super::*;
+    use crate::encoding::AttributeEncoder;
+    use crate::ffi::errors::ERROR_INVALID_DATE;
+    use amcl_wrapper::field_elem::FieldElement;
+    use std::ffi::CString;
+
+    #[test]
+    fn encode_test() {
+        let mut err = ExternError::success();
+        let buffer = aries_credx_bls381_encode_from_isize(-1, &mut err);
+        assert!(err.get_code().is_success());
+        assert_eq!(FieldElement::to_vec(&FieldElement::encode_from_isize(-1isize).unwrap()), buffer.destroy_into_vec());
+
+        let buffer = aries_credx_bls381_encoded_null(&mut err);
+        assert!(err.get_code().is_success());
+        assert_eq!(FieldElement::to_vec(&FieldElement::encoded_null().unwrap()), buffer.destroy_into_vec());
+
+        let date = CString::new("1982-12-20T10:45:00.000-06:00").unwrap();
+        let buffer = aries_credx_bls381_encode_from_rfc3339_as_dayssince1900(FfiStr::from_cstr(&date), &mut err);
+        assert!(err.get_code().is_success());
+        assert_eq!(FieldElement::to_vec(&(FieldElement::zero_center() + FieldElement::from(30303))), buffer.destroy_into_vec());
+
+        let date = CString::new("1900").unwrap();
+        let buffer = aries_credx_bls381_encode_from_rfc3339_as_unixtimestamp(FfiStr::from_cstr(&date), &mut err);
+        assert_eq!(ERROR_INVALID_DATE, err.get_code().code());
+        assert!(buffer.destroy_into_vec().is_empty());
+
+        let value = CString::new("Alice").unwrap();
+        let algorithm = CString::new("blake2b-256").unwrap();
+        let buffer = aries_credx_bls381_encode_from_utf8_as_hash_with(FfiStr::from_cstr(&value), FfiStr::from_cstr(&algorithm), &mut err);
+        assert!(err.get_code().is_success());
+        assert_eq!(FieldElement::to_vec(&HashAlgorithm::Blake2b256.encode::<FieldElement>("Alice").unwrap()), buffer.destroy_into_vec());
+
+        let algorithm = CString::new("md5").unwrap();
+        let buffer = aries_credx_bls381_encode_from_utf8_as_hash_with(FfiStr::from_cstr(&value), FfiStr::from_cstr(&algorithm), &mut err);
+        assert!(!err.get_code().is_success());
+        assert!(buffer.destroy_into_vec().is_empty());
+    }
+}
@@ -0,0 +1,31 @@
+use crate::errors::{Error, ErrorKind};
+
+use ffi_support::{ErrorCode, ExternError};
+
+/// The input is not a valid date
+pub const ERROR_INVALID_DATE: i32 = 1;
+/// The input is not valid for the requested conversion
+pub const ERROR_INVALID_INPUT: i32 = 2;
+/// The value does not fit in the range supported by the encoding
+pub const ERROR_OUT_OF_RANGE: i32 = 3;
+/// The cryptographic backend failed
+pub const ERROR_BACKEND: i32 = 4;
+
+impl From<ErrorKind> for ErrorCode {
+    fn from(kind: ErrorKind) -> Self {
+        ErrorCode::new(match kind {
+            ErrorKind::InvalidDate => ERROR_INVALID_DATE,
+            ErrorKind::InvalidInput => ERROR_INVALID_INPUT,
+            ErrorKind::OutOfRange => ERROR_OUT_OF_RANGE,
+            ErrorKind::Backend => ERROR_BACKEND,
+        })
+    }
+}
+
+impl From<Error> for ExternError {
+    fn from(e: Error) -> Self {
+        ExternError::new_error(e.kind().into(), e.message())
+    }
+}
+
+ffi_support::define_string_destructor!(aries_credx_string_free);
\ No newline at end of file
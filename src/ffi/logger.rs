@@ -3,16 +3,19 @@ use ffi_support::FfiStr;
 use log::{LevelFilter, Metadata, Record};
 use std::{
     env,
-    ffi::CString,
+    ffi::{CStr, CString},
     io::Write,
     ptr,
-    os::raw::{c_char, c_void}
+    os::raw::c_void
 };
 
+/// Opaque context passed back to every callback
 pub type LogContext = *const c_void;
 
+/// Callback to check whether a log level is enabled for a target
 pub type EnabledCB = extern "C" fn(context: *const c_void, level: u32, target: FfiStr<'_>) -> bool;
 
+/// Callback to write a log record
 pub type LogCB = extern "C" fn(context: LogContext,
                                level: u32,
                                target: FfiStr<'_>,
@@ -21,8 +24,10 @@ pub type LogCB = extern "C" fn(context: LogContext,
                                file: FfiStr<'_>,
                                line: u32);
 
+/// Callback to flush buffered log records
 pub type FlushCB = extern "C" fn(context: *const c_void);
 
+/// Logger that forwards records to callbacks supplied over the C API
 pub struct AriesCredXFrameworkLogger {
     context: LogContext,
     enabled: Option<EnabledCB>,
@@ -52,7 +57,7 @@ impl log::Log for AriesCredXFrameworkLogger {
             let level = metadata.level() as u32;
             let target = CString::new(metadata.target()).unwrap();
 
-            enabled_cb(self.context, level, target.as_ptr())
+            enabled_cb(self.context, level, FfiStr::from_cstr(&target))
         } else {
             true
         }
@@ -72,13 +77,10 @@ impl log::Log for AriesCredXFrameworkLogger {
         log_cb(
             self.context,
             level,
-            target.as_ptr(),
-            message.as_ptr(),
-            module_path
-                .as_ref()
-                .map(|p| p.as_ptr())
-                .unwrap_or(ptr::null()),
-            file.as_ref().map(|p| p.as_ptr()).unwrap_or(ptr::null()),
+            FfiStr::from_cstr(&target),
+            FfiStr::from_cstr(&message),
+            to_ffi_str(module_path.as_deref()),
+            to_ffi_str(file.as_deref()),
             line,
         )
     }
@@ -90,11 +92,20 @@ impl log::Log for AriesCredXFrameworkLogger {
     }
 }
 
+/// A null `FfiStr` for `None`, which `FfiStr::as_opt_str` returns as `None`
+fn to_ffi_str(value: Option<&CStr>) -> FfiStr<'_> {
+    match value {
+        Some(s) => FfiStr::from_cstr(s),
+        None => unsafe { FfiStr::from_raw(ptr::null()) },
+    }
+}
+
 unsafe impl Sync for AriesCredXFrameworkLogger {}
 
 unsafe impl Send for AriesCredXFrameworkLogger {}
 
 impl AriesCredXFrameworkLogger {
+    /// Install the callback logger as the global logger
     pub fn init(
         context: LogContext,
         enabled: Option<EnabledCB>,
@@ -103,16 +114,18 @@ impl AriesCredXFrameworkLogger {
     ) -> Result<(), String> {
         let logger = AriesCredXFrameworkLogger::new(context, enabled, log, flush);
 
-        log::set_boxed_logger(Box::new(logger))?;
+        log::set_boxed_logger(Box::new(logger)).map_err(|e| e.to_string())?;
         log::set_max_level(LevelFilter::Trace);
 
         Ok(())
     }
 }
 
+/// Logger that writes to stderr using `env_logger`
 pub struct AriesCredXFrameworkDefaultLogger;
 
 impl AriesCredXFrameworkDefaultLogger {
+    /// Install `env_logger` filtered by `pattern` or `RUST_LOG`
     pub fn init(pattern: Option<String>) -> Result<(), String> {
         let pattern = pattern.or_else(|| env::var("RUST_LOG").ok());
 
@@ -129,8 +142,9 @@ impl AriesCredXFrameworkDefaultLogger {
                 )
             })
             .filter(None, LevelFilter::Off)
-            .parse_filters(pattern.as_ref().map(String::as_str).unwrap_or(""))
-            .try_init()?;
+            .parse_filters(pattern.as_deref().unwrap_or(""))
+            .try_init()
+            .map_err(|e| e.to_string())?;
 
         Ok(())
     }
@@ -1,2 +1,7 @@
+/// C functions for encoding attributes
+#[cfg(any(feature = "bls377", feature = "bls381", feature = "bn254", feature = "ristretto255", feature = "rsa-native", feature = "rsa-pure", feature = "secp256k1", feature = "secp256r1"))]
 pub mod encoding;
+/// Mapping of framework errors to `ExternError`
+pub mod errors;
+/// Logging callbacks for consumers of the C API
 pub mod logger;
\ No newline at end of file
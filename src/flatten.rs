@@ -0,0 +1,349 @@
+use crate::encoding::AttributeEncoder;
+use crate::errors::{Error, ErrorKind};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// The last token of the attribute that holds an array's length.
+/// It never collides with a member name because `~` in a member name is
+/// escaped to `~0`, so a member named `"~length"` becomes the token `"~0length"`
+const LENGTH_TOKEN: &str = "~length";
+
+/// The last token of the attribute that marks an object without members,
+/// escaped the same way as `LENGTH_TOKEN`
+const EMPTY_TOKEN: &str = "~empty";
+
+/// The longest array `unflatten` will rebuild. The length attribute is chosen
+/// by whoever reveals it, so it is checked before the array is allocated
+pub const MAX_ARRAY_LENGTH: u64 = 65_536;
+
+/// The most array elements `unflatten` will allocate for all arrays together,
+/// so nested length attributes cannot multiply the allocation
+pub const MAX_ARRAY_ELEMENTS: u64 = 1_048_576;
+
+/// A single scalar from a JSON document
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct FlatAttribute {
+    /// The JSON pointer (RFC 6901) to the value, `<pointer to array>/~length`
+    /// for the number of elements in an array or `<pointer to object>/~empty`
+    /// for an object without members
+    pub pointer: String,
+    /// The scalar value
+    pub value: Value,
+}
+
+impl FlatAttribute {
+    /// Encode the value with the encoder matching its JSON type.
+    /// Strings are hashed with SHA-256, integers use `encode_from_i64` or
+    /// `encode_from_u64`, other numbers `encode_from_f64`, booleans `encode_from_bool`
+    /// and null `encoded_null`
+    pub fn encode<E: AttributeEncoder + ?Sized>(&self) -> Result<E::Output, Error> {
+        let result = match &self.value {
+            Value::Null => E::encoded_null(),
+            Value::Bool(b) => E::encode_from_bool(*b),
+            Value::String(s) => E::encode_from_utf8_as_hash::<_, sha2::Sha256>(s.as_str()),
+            Value::Number(n) => {
+                if let Some(i) = n.as_i64() {
+                    E::encode_from_i64(i)
+                } else if let Some(u) = n.as_u64() {
+                    E::encode_from_u64(u)
+                } else {
+                    E::encode_from_f64(n.as_f64().unwrap_or(f64::NAN))
+                }
+            },
+            Value::Array(_) | Value::Object(_) => Err(Error::new(ErrorKind::InvalidInput, "expected a scalar")),
+        };
+        result.map_err(|e| Error::new(e.kind(), format!("{}: {}", self.pointer, e.message())))
+    }
+}
+
+/// Flatten `document` into its scalar values in a canonical order.
+/// Object members are visited in sorted key order and array elements by index,
+/// with each array's length attribute before its elements.
+/// Empty objects have no scalars so they are marked with a `true` attribute
+pub fn flatten(document: &Value) -> Vec<FlatAttribute> {
+    let mut attributes = Vec::new();
+    flatten_into(document, String::new(), &mut attributes);
+    attributes
+}
+
+fn flatten_into(value: &Value, pointer: String, attributes: &mut Vec<FlatAttribute>) {
+    match value {
+        Value::Object(o) if o.is_empty() => {
+            attributes.push(FlatAttribute { pointer: format!("{}/{}", pointer, EMPTY_TOKEN), value: Value::Bool(true) });
+        },
+        Value::Object(o) => {
+            let mut keys = o.keys().collect::<Vec<&String>>();
+            keys.sort();
+            for key in keys {
+                flatten_into(&o[key], format!("{}/{}", pointer, escape(key)), attributes);
+            }
+        },
+        Value::Array(a) => {
+            attributes.push(FlatAttribute { pointer: format!("{}/{}", pointer, LENGTH_TOKEN), value: Value::from(a.len()) });
+            for (i, v) in a.iter().enumerate() {
+                flatten_into(v, format!("{}/{}", pointer, i), attributes);
+            }
+        },
+        _ => attributes.push(FlatAttribute { pointer, value: value.clone() }),
+    }
+}
+
+/// Flatten `document` and encode every scalar in canonical order
+pub fn encode<E: AttributeEncoder + ?Sized>(document: &Value) -> Result<Vec<E::Output>, Error> {
+    flatten(document).iter().map(|a| a.encode::<E>()).collect()
+}
+
+/// Rebuild a document from some or all of its flattened attributes, i.e. the
+/// values revealed in a presentation.
+/// Arrays are only rebuilt as arrays when their length attribute is included,
+/// otherwise their elements become members of an object keyed by index.
+/// Elements that are not included are null.
+/// Fails with `OutOfRange` if a length is above `MAX_ARRAY_LENGTH` or the
+/// lengths add up to more than `MAX_ARRAY_ELEMENTS`
+pub fn unflatten(attributes: &[FlatAttribute]) -> Result<Value, Error> {
+    let mut parsed = attributes
+        .iter()
+        .map(|a| parse(a.pointer.as_str()).map(|(tokens, marker)| (tokens, marker, a)))
+        .collect::<Result<Vec<_>, Error>>()?;
+    // Create outer arrays before the arrays and values they contain,
+    // and empty objects last so they conflict with any members
+    parsed.sort_by_key(|(tokens, marker, a)| (*marker != Marker::Length, *marker == Marker::Empty, tokens.len(), a.pointer.clone()));
+
+    let mut root = Value::Null;
+    let mut elements = 0u64;
+    for (tokens, marker, attribute) in parsed {
+        let conflict = || Error::new(ErrorKind::InvalidInput, format!("{}: conflicts with another attribute", attribute.pointer));
+        let value = match marker {
+            Marker::Length => {
+                let length = attribute.value
+                    .as_u64()
+                    .ok_or_else(|| Error::new(ErrorKind::InvalidInput, format!("{}: expected an array length", attribute.pointer)))?;
+                if length > MAX_ARRAY_LENGTH {
+                    return Err(Error::new(ErrorKind::OutOfRange, format!("{}: {} is above the maximum array length {}", attribute.pointer, length, MAX_ARRAY_LENGTH)));
+                }
+                elements += length;
+                if elements > MAX_ARRAY_ELEMENTS {
+                    return Err(Error::new(ErrorKind::OutOfRange, format!("{}: the arrays have more than {} elements together", attribute.pointer, MAX_ARRAY_ELEMENTS)));
+                }
+                Value::Array(vec![Value::Null; length as usize])
+            },
+            Marker::Empty if attribute.value == Value::Bool(true) => Value::Object(Map::new()),
+            Marker::Empty => return Err(Error::new(ErrorKind::InvalidInput, format!("{}: expected true", attribute.pointer))),
+            Marker::None => attribute.value.clone(),
+        };
+        let slot = walk(&mut root, tokens.as_slice(), attribute.pointer.as_str())?;
+        match slot {
+            Value::Null => *slot = value,
+            _ => return Err(conflict()),
+        }
+    }
+    Ok(root)
+}
+
+/// What the last token of a pointer refers to
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum Marker {
+    /// The value itself
+    None,
+    /// The length of the array at the other tokens
+    Length,
+    /// An empty object at the other tokens
+    Empty,
+}
+
+/// Find the value at `tokens`, creating objects for missing containers
+fn walk<'a>(root: &'a mut Value, tokens: &[String], pointer: &str) -> Result<&'a mut Value, Error> {
+    let mut current = root;
+    for token in tokens {
+        if current.is_null() {
+            *current = Value::Object(Map::new());
+        }
+        current = match current {
+            Value::Object(o) => o.entry(token.clone()).or_insert(Value::Null),
+            Value::Array(a) => {
+                let length = a.len();
+                token
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(move |i| a.get_mut(i))
+                    .ok_or_else(|| Error::new(ErrorKind::InvalidInput, format!("{}: index is not below the array length {}", pointer, length)))?
+            },
+            _ => return Err(Error::new(ErrorKind::InvalidInput, format!("{}: conflicts with another attribute", pointer))),
+        };
+    }
+    Ok(current)
+}
+
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Split a JSON pointer into unescaped tokens.
+/// If the last token is the array length or empty object token it is removed
+/// and returned as a `Marker` with the tokens of the container. It is compared
+/// before unescaping so a member named `"~length"` is not mistaken for it
+fn parse(pointer: &str) -> Result<(Vec<String>, Marker), Error> {
+    if pointer.is_empty() {
+        return Ok((Vec::new(), Marker::None));
+    }
+    let invalid = || Error::new(ErrorKind::InvalidInput, format!("{}: not a valid JSON pointer", pointer));
+    let rest = pointer.strip_prefix('/').ok_or_else(invalid)?;
+    let mut tokens = rest.split('/').collect::<Vec<&str>>();
+    let marker = match tokens.last() {
+        Some(&LENGTH_TOKEN) => Marker::Length,
+        Some(&EMPTY_TOKEN) => Marker::Empty,
+        _ => Marker::None,
+    };
+    if marker != Marker::None {
+        tokens.pop();
+    }
+    tokens
+        .iter()
+        .map(|t| {
+            let mut chars = t.chars();
+            let mut token = String::with_capacity(t.len());
+            while let Some(c) = chars.next() {
+                if c != '~' {
+                    token.push(c);
+                    continue;
+                }
+                match chars.next() {
+                    Some('0') => token.push('~'),
+                    Some('1') => token.push('/'),
+                    _ => return Err(invalid()),
+                }
+            }
+            Ok(token)
+        })
+        .collect::<Result<Vec<String>, Error>>()
+        .map(|tokens| (tokens, marker))
+}
+
+#[cfg(all(test, feature = "rsa-pure"))]
+mod tests {
+    use super::*;
+    use crate::encoding::rsa_pure::BigInteger;
+    use serde_json::json;
+
+    fn document() -> Value {
+        json!({
+            "name": "Alice",
+            "address": { "street/unit": "12 Main St", "postal_code": "84101" },
+            "degrees": [
+                { "type": "BSc", "year": 2004 },
+                { "type": "MSc", "year": 2006, "honors": true }
+            ],
+            "gpa": 3.9,
+            "middle_name": null,
+            "tags": []
+        })
+    }
+
+    #[test]
+    fn flatten_test() {
+        let pointers = flatten(&document()).into_iter().map(|a| a.pointer).collect::<Vec<String>>();
+        assert_eq!(vec![
+            "/address/postal_code",
+            "/address/street~1unit",
+            "/degrees/~length",
+            "/degrees/0/type",
+            "/degrees/0/year",
+            "/degrees/1/honors",
+            "/degrees/1/type",
+            "/degrees/1/year",
+            "/gpa",
+            "/middle_name",
+            "/name",
+            "/tags/~length",
+        ], pointers);
+        assert_eq!(document(), unflatten(&flatten(&document())).unwrap());
+        assert_eq!(json!("scalar"), unflatten(&flatten(&json!("scalar"))).unwrap());
+        assert_eq!(json!([[1], []]), unflatten(&flatten(&json!([[1], []]))).unwrap());
+    }
+
+    #[test]
+    fn empty_container_test() {
+        assert_eq!("/a/~empty", flatten(&json!({ "a": {} }))[0].pointer);
+        for document in [json!({}), json!({ "a": {} }), json!({ "a": [] }), json!({ "a": [{}], "b": { "c": {} } }), json!({ "~empty": true })].iter() {
+            assert_eq!(document, &unflatten(&flatten(document)).unwrap());
+        }
+        let invalid = vec![FlatAttribute { pointer: "/a/~empty".to_string(), value: json!(false) }];
+        assert_eq!(ErrorKind::InvalidInput, unflatten(&invalid).unwrap_err().kind());
+        let conflicting = vec![
+            FlatAttribute { pointer: "/a/~empty".to_string(), value: json!(true) },
+            FlatAttribute { pointer: "/a/b".to_string(), value: json!(1) },
+        ];
+        assert_eq!(ErrorKind::InvalidInput, unflatten(&conflicting).unwrap_err().kind());
+    }
+
+    #[test]
+    fn encode_test() {
+        let res = encode::<BigInteger>(&document()).unwrap();
+        assert_eq!(12, res.len());
+        assert_eq!(BigInteger::encode_from_utf8_as_hash::<_, sha2::Sha256>("84101").unwrap(), res[0]);
+        assert_eq!(BigInteger::encode_from_u64(2u64).unwrap(), res[2]);
+        assert_eq!(BigInteger::encode_from_i64(2004i64).unwrap(), res[4]);
+        assert_eq!(BigInteger::encode_from_bool(true).unwrap(), res[5]);
+        assert_eq!(BigInteger::encode_from_f64(3.9).unwrap(), res[8]);
+        assert_eq!(BigInteger::encoded_null().unwrap(), res[9]);
+
+        let wide = encode::<BigInteger>(&json!([u64::MAX, i64::MIN])).unwrap();
+        assert_eq!(BigInteger::encode_from_u64(u64::MAX).unwrap(), wide[1]);
+        assert_eq!(BigInteger::encode_from_i64(i64::MIN).unwrap(), wide[2]);
+
+        let attribute = FlatAttribute { pointer: "/degrees".to_string(), value: json!([]) };
+        assert_eq!("/degrees: expected a scalar", attribute.encode::<BigInteger>().unwrap_err().message());
+    }
+
+    #[test]
+    fn selective_disclosure_test() {
+        let revealed = flatten(&document())
+            .into_iter()
+            .filter(|a| a.pointer.starts_with("/degrees/~") || a.pointer.starts_with("/degrees/1/") || a.pointer == "/name")
+            .collect::<Vec<FlatAttribute>>();
+        assert_eq!(json!({
+            "name": "Alice",
+            "degrees": [null, { "type": "MSc", "year": 2006, "honors": true }]
+        }), unflatten(&revealed).unwrap());
+
+        let revealed = flatten(&document())
+            .into_iter()
+            .filter(|a| a.pointer == "/degrees/1/type")
+            .collect::<Vec<FlatAttribute>>();
+        assert_eq!(json!({ "degrees": { "1": { "type": "MSc" } } }), unflatten(&revealed).unwrap());
+
+        let conflicting = vec![
+            FlatAttribute { pointer: "/a".to_string(), value: json!(1) },
+            FlatAttribute { pointer: "/a/b".to_string(), value: json!(2) },
+        ];
+        assert_eq!(ErrorKind::InvalidInput, unflatten(&conflicting).unwrap_err().kind());
+        let out_of_bounds = vec![
+            FlatAttribute { pointer: "/a/~length".to_string(), value: json!(1) },
+            FlatAttribute { pointer: "/a/1".to_string(), value: json!(2) },
+        ];
+        assert_eq!(ErrorKind::InvalidInput, unflatten(&out_of_bounds).unwrap_err().kind());
+        let tilde = json!({ "~length": 1, "list": ["x"] });
+        assert_eq!("/~0length", flatten(&tilde).last().unwrap().pointer);
+        assert_eq!(tilde, unflatten(&flatten(&tilde)).unwrap());
+        let invalid = vec![FlatAttribute { pointer: "a~2".to_string(), value: json!(1) }];
+        assert_eq!(ErrorKind::InvalidInput, unflatten(&invalid).unwrap_err().kind());
+    }
+
+    #[test]
+    fn array_length_test() {
+        let huge = vec![FlatAttribute { pointer: "/a/~length".to_string(), value: json!(u64::MAX) }];
+        assert_eq!(ErrorKind::OutOfRange, unflatten(&huge).unwrap_err().kind());
+        let above = vec![FlatAttribute { pointer: "/a/~length".to_string(), value: json!(MAX_ARRAY_LENGTH + 1) }];
+        assert_eq!(ErrorKind::OutOfRange, unflatten(&above).unwrap_err().kind());
+        let largest = vec![FlatAttribute { pointer: "/a/~length".to_string(), value: json!(MAX_ARRAY_LENGTH) }];
+        assert_eq!(MAX_ARRAY_LENGTH as usize, unflatten(&largest).unwrap()["a"].as_array().unwrap().len());
+
+        let nested = (0..MAX_ARRAY_ELEMENTS / MAX_ARRAY_LENGTH)
+            .map(|i| FlatAttribute { pointer: format!("/a/{}/~length", i), value: json!(MAX_ARRAY_LENGTH) })
+            .chain(std::iter::once(FlatAttribute { pointer: "/a/~length".to_string(), value: json!(MAX_ARRAY_LENGTH) }))
+            .collect::<Vec<FlatAttribute>>();
+        assert_eq!(ErrorKind::OutOfRange, unflatten(&nested).unwrap_err().kind());
+        assert!(unflatten(&nested[1..]).is_ok());
+    }
+}
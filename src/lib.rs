@@ -12,5 +12,18 @@
 /// of a specific format into a integer suitable for cryptographic signing.
 pub mod encoding;
 
+/// Errors that can be returned by the framework
+pub mod errors;
+
+/// Flattening of nested credential documents into JSON pointer named attributes
+#[cfg(feature = "schema")]
+pub mod flatten;
+
+/// Schemas that declare how each attribute in a credential is encoded
+#[cfg(feature = "schema")]
+pub mod schema;
+
+/// C API for consumers in other languages
 #[cfg(feature = "ffi")]
+#[allow(unsafe_code)]
 pub mod ffi;
\ No newline at end of file
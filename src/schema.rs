@@ -0,0 +1,413 @@
+use crate::encoding::{hash::HashAlgorithm, AttributeEncoder};
+use crate::errors::{Error, ErrorKind};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// The encoding rule used for an attribute
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AttributeType {
+    /// A JSON integer encoded with `encode_from_i64` or `encode_from_u64`
+    Integer,
+    /// A JSON number encoded with `encode_from_f64`
+    Float,
+    /// An RFC3339 string encoded with `encode_from_rfc3339_as_unixtimestamp`
+    DateUnix,
+    /// An RFC3339 string encoded with `encode_from_rfc3339_as_dayssince1900`
+    DateDaysSince1900,
+    /// An RFC3339 string encoded with `encode_from_rfc3339_as_unixtimestamp_millis`
+    DateUnixMillis,
+    /// An ISO 8601 date like a birthdate, encoded with `encode_from_iso8601_date_as_dayssince1900`
+    Date,
+    /// An ISO 8601 time of day encoded with `encode_from_iso8601_time_as_milliseconds`
+    Time,
+    /// An ISO 8601 duration encoded with `encode_from_iso8601_duration_as_milliseconds`
+    Duration,
+    /// A string encoded as its hash with `algorithm`
+    StringHash {
+        /// The hash algorithm, SHA-256 when not specified
+        #[serde(default)]
+        algorithm: HashAlgorithm,
+    },
+    /// A string that must be one of `variants`, encoded with `encode_from_enum`
+    Enum {
+        /// The declared ordered list of all variants
+        variants: Vec<String>,
+    },
+    /// A JSON boolean encoded with `encode_from_bool`
+    Bool,
+}
+
+impl AttributeType {
+    /// The name used in error messages
+    fn expected(&self) -> &'static str {
+        match self {
+            AttributeType::Integer => "integer",
+            AttributeType::Float => "number",
+            AttributeType::DateUnix | AttributeType::DateDaysSince1900 | AttributeType::DateUnixMillis | AttributeType::Date => "date",
+            AttributeType::Time => "time",
+            AttributeType::Duration => "duration",
+            AttributeType::StringHash { .. } => "string",
+            AttributeType::Enum { .. } => "enum variant",
+            AttributeType::Bool => "boolean",
+        }
+    }
+}
+
+/// Describes how a single attribute is encoded
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AttributeDescriptor {
+    /// The attribute name. A `.` separates the fields of nested objects
+    pub name: String,
+    /// The encoding rule for the attribute
+    #[serde(flatten)]
+    pub attribute_type: AttributeType,
+    /// Whether a null or missing value is allowed and encoded as `encoded_null()`
+    #[serde(default)]
+    pub nullable: bool,
+}
+
+impl AttributeDescriptor {
+    /// Create a new attribute descriptor
+    pub fn new<A: Into<String>>(name: A, attribute_type: AttributeType, nullable: bool) -> Self {
+        Self { name: name.into(), attribute_type, nullable }
+    }
+
+    /// Encode a single JSON value according to this descriptor.
+    /// `None` means the value was not provided
+    pub fn encode<E: AttributeEncoder + ?Sized>(&self, value: Option<&Value>) -> Result<E::Output, Error> {
+        let value = match value {
+            None | Some(Value::Null) if self.nullable => return E::encoded_null(),
+            None => return Err(self.error(ErrorKind::InvalidInput, "missing value")),
+            Some(v) => v,
+        };
+        let mismatch = || self.error(ErrorKind::InvalidInput, format!("expected {}", self.attribute_type.expected()));
+        let result = match (&self.attribute_type, value) {
+            (AttributeType::Integer, Value::Number(n)) => {
+                if let Some(i) = n.as_i64() {
+                    E::encode_from_i64(i)
+                } else if let Some(u) = n.as_u64() {
+                    E::encode_from_u64(u)
+                } else {
+                    return Err(mismatch());
+                }
+            },
+            (AttributeType::Float, Value::Number(n)) => E::encode_from_f64(n.as_f64().ok_or_else(mismatch)?),
+            (AttributeType::DateUnix, Value::String(s)) => E::encode_from_rfc3339_as_unixtimestamp(s.as_str()),
+            (AttributeType::DateDaysSince1900, Value::String(s)) => E::encode_from_rfc3339_as_dayssince1900(s.as_str()),
+            (AttributeType::DateUnixMillis, Value::String(s)) => E::encode_from_rfc3339_as_unixtimestamp_millis(s.as_str()),
+            (AttributeType::Date, Value::String(s)) => E::encode_from_iso8601_date_as_dayssince1900(s.as_str()),
+            (AttributeType::Time, Value::String(s)) => E::encode_from_iso8601_time_as_milliseconds(s.as_str()),
+            (AttributeType::Duration, Value::String(s)) => E::encode_from_iso8601_duration_as_milliseconds(s.as_str()),
+            (AttributeType::StringHash { algorithm }, Value::String(s)) => algorithm.encode::<E>(s.as_str()),
+            (AttributeType::Enum { variants }, Value::String(s)) => E::encode_from_enum(s, variants.as_slice()),
+            (AttributeType::Bool, Value::Bool(b)) => E::encode_from_bool(*b),
+            _ => return Err(mismatch()),
+        };
+        result.map_err(|e| self.error(e.kind(), e.message()))
+    }
+
+    fn error<M: std::fmt::Display>(&self, kind: ErrorKind, message: M) -> Error {
+        Error::new(kind, format!("{}: {}", self.name, message))
+    }
+}
+
+/// The ordered list of attributes in a credential and how each is encoded
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(try_from = "Vec<AttributeDescriptor>", into = "Vec<AttributeDescriptor>")]
+pub struct Schema {
+    attributes: Vec<AttributeDescriptor>,
+}
+
+impl Schema {
+    /// Create a new schema.
+    /// Fails if an attribute name is used more than once, an attribute name is
+    /// also the object of another attribute like `address` and `address.postal_code`,
+    /// or an enumeration declares no variants or the same variant twice
+    pub fn new(attributes: Vec<AttributeDescriptor>) -> Result<Self, Error> {
+        for (i, a) in attributes.iter().enumerate() {
+            if attributes[..i].iter().any(|b| b.name == a.name) {
+                return Err(Error::new(ErrorKind::InvalidInput, format!("{}: declared more than once", a.name)));
+            }
+            let nested = format!("{}.", a.name);
+            if let Some(b) = attributes.iter().find(|b| b.name.starts_with(nested.as_str())) {
+                return Err(Error::new(ErrorKind::InvalidInput, format!("{}: declared as an attribute and as the object of {}", a.name, b.name)));
+            }
+            if let AttributeType::Enum { variants } = &a.attribute_type {
+                if variants.is_empty() || variants.iter().enumerate().any(|(j, v)| variants[..j].contains(v)) {
+                    return Err(Error::new(ErrorKind::InvalidInput, format!("{}: variants must be non-empty and unique", a.name)));
+                }
+            }
+        }
+        Ok(Self { attributes })
+    }
+
+    /// The attributes in encoding order
+    pub fn attributes(&self) -> &[AttributeDescriptor] {
+        self.attributes.as_slice()
+    }
+}
+
+impl std::convert::TryFrom<Vec<AttributeDescriptor>> for Schema {
+    type Error = Error;
+
+    fn try_from(attributes: Vec<AttributeDescriptor>) -> Result<Self, Error> {
+        Schema::new(attributes)
+    }
+}
+
+impl From<Schema> for Vec<AttributeDescriptor> {
+    fn from(schema: Schema) -> Self {
+        schema.attributes
+    }
+}
+
+/// Encode every attribute in `schema` from the JSON credential body `credential`
+/// and return the encoded values in schema order.
+/// Attribute names containing `.` refer to nested objects, i.e. `address.postal_code`
+/// is the `postal_code` field of the `address` object.
+/// Fails if `credential` is not an object, has attributes not in `schema`,
+/// or a value does not match its declared type. Error messages start with
+/// the path of the offending value, i.e. "address.postal_code: expected date"
+pub fn encode<E: AttributeEncoder + ?Sized>(schema: &Schema, credential: &Value) -> Result<Vec<E::Output>, Error> {
+    let object = credential
+        .as_object()
+        .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "Expected a JSON object"))?;
+    check_declared(schema, object, "")?;
+    schema.attributes
+        .iter()
+        .map(|a| a.encode::<E>(lookup(object, a.name.as_str())?))
+        .collect()
+}
+
+/// Find the value at the dotted `path`.
+/// Returns `None` if any object along the path does not have the field
+fn lookup<'a>(object: &'a Map<String, Value>, path: &str) -> Result<Option<&'a Value>, Error> {
+    let mut current = object;
+    let mut parts = path.split('.').peekable();
+    let mut offset = 0;
+    while let Some(part) = parts.next() {
+        offset += part.len();
+        let value = match current.get(part) {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        if parts.peek().is_none() {
+            return Ok(Some(value));
+        }
+        current = match value {
+            Value::Object(o) => o,
+            Value::Null => return Ok(None),
+            _ => return Err(Error::new(ErrorKind::InvalidInput, format!("{}: expected object", &path[..offset]))),
+        };
+        offset += 1;
+    }
+    Ok(None)
+}
+
+/// Ensure every field in `object` is declared in `schema` or is an object
+/// containing declared fields. A null object is treated as having no fields
+fn check_declared(schema: &Schema, object: &Map<String, Value>, prefix: &str) -> Result<(), Error> {
+    for (key, value) in object {
+        let path = format!("{}{}", prefix, key);
+        if schema.attributes.iter().any(|a| a.name == path) {
+            continue;
+        }
+        let nested = format!("{}.", path);
+        if !schema.attributes.iter().any(|a| a.name.starts_with(nested.as_str())) {
+            return Err(Error::new(ErrorKind::InvalidInput, format!("{}: not declared in the schema", path)));
+        }
+        match value {
+            Value::Object(o) => check_declared(schema, o, nested.as_str())?,
+            Value::Null => {},
+            _ => return Err(Error::new(ErrorKind::InvalidInput, format!("{}: expected object", path))),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "rsa-pure"))]
+mod tests {
+    use super::*;
+    use crate::encoding::rsa_pure::BigInteger;
+    use serde_json::json;
+
+    fn schema() -> Schema {
+        Schema::new(vec![
+            AttributeDescriptor::new("name", AttributeType::StringHash { algorithm: HashAlgorithm::Sha256 }, false),
+            AttributeDescriptor::new("age", AttributeType::Integer, false),
+            AttributeDescriptor::new("height", AttributeType::Float, false),
+            AttributeDescriptor::new("birthdate", AttributeType::DateDaysSince1900, false),
+            AttributeDescriptor::new("issued", AttributeType::DateUnix, false),
+            AttributeDescriptor::new("document", AttributeType::Enum { variants: vec!["passport".to_string(), "drivers_license".to_string()] }, false),
+            AttributeDescriptor::new("veteran", AttributeType::Bool, true),
+        ]).unwrap()
+    }
+
+    #[test]
+    fn encode_test() {
+        let values = json!({
+            "name": "Alice",
+            "age": 30,
+            "height": 1.8,
+            "birthdate": "1982-12-20T10:45:00.000-06:00",
+            "issued": "2018-01-26T18:30:09.453+00:00",
+            "document": "drivers_license",
+            "veteran": null,
+        });
+        let res = encode::<BigInteger>(&schema(), &values).unwrap();
+        assert_eq!(vec![
+            BigInteger::encode_from_utf8_as_hash::<_, sha2::Sha256>("Alice").unwrap(),
+            BigInteger::encode_from_i64(30i64).unwrap(),
+            BigInteger::encode_from_f64(1.8).unwrap(),
+            BigInteger::encode_from_rfc3339_as_dayssince1900("1982-12-20T10:45:00.000-06:00").unwrap(),
+            BigInteger::encode_from_rfc3339_as_unixtimestamp("2018-01-26T18:30:09.453+00:00").unwrap(),
+            BigInteger::zero_center() + BigInteger::from(1u64),
+            BigInteger::encoded_null().unwrap(),
+        ], res);
+
+        let mut missing = values.clone();
+        missing.as_object_mut().unwrap().remove("veteran");
+        assert_eq!(res, encode::<BigInteger>(&schema(), &missing).unwrap());
+
+        let mut wide = values.clone();
+        wide["age"] = json!(u64::MAX);
+        assert_eq!(BigInteger::encode_from_u64(u64::MAX).unwrap(), encode::<BigInteger>(&schema(), &wide).unwrap()[1]);
+        wide["age"] = json!(i64::MIN);
+        assert_eq!(BigInteger::encode_from_i64(i64::MIN).unwrap(), encode::<BigInteger>(&schema(), &wide).unwrap()[1]);
+    }
+
+    #[test]
+    fn date_time_test() {
+        let schema = Schema::new(vec![
+            AttributeDescriptor::new("birthdate", AttributeType::Date, false),
+            AttributeDescriptor::new("opens", AttributeType::Time, false),
+            AttributeDescriptor::new("validity", AttributeType::Duration, false),
+            AttributeDescriptor::new("issued", AttributeType::DateUnixMillis, false),
+        ]).unwrap();
+        let values = json!({
+            "birthdate": "1899-07-04",
+            "opens": "08:30",
+            "validity": "P52W",
+            "issued": "2018-01-26T18:30:09.453+00:00",
+        });
+        assert_eq!(vec![
+            BigInteger::encode_from_iso8601_date_as_dayssince1900("1899-07-04").unwrap(),
+            BigInteger::encode_from_iso8601_time_as_milliseconds("08:30").unwrap(),
+            BigInteger::encode_from_iso8601_duration_as_milliseconds("P52W").unwrap(),
+            BigInteger::encode_from_rfc3339_as_unixtimestamp_millis("2018-01-26T18:30:09.453+00:00").unwrap(),
+        ], encode::<BigInteger>(&schema, &values).unwrap());
+
+        let mut values = values;
+        values["validity"] = json!(3600);
+        assert_eq!("validity: expected duration", encode::<BigInteger>(&schema, &values).unwrap_err().message());
+    }
+
+    #[test]
+    fn reject_test() {
+        let values = json!({
+            "name": "Alice",
+            "age": "30",
+            "height": 1.8,
+            "birthdate": "1982-12-20T10:45:00.000-06:00",
+            "issued": "2018-01-26T18:30:09.453+00:00",
+            "document": "drivers_license",
+        });
+        let err = encode::<BigInteger>(&schema(), &values).unwrap_err();
+        assert_eq!(ErrorKind::InvalidInput, err.kind());
+        assert_eq!("age: expected integer", err.message());
+
+        let mut values = values;
+        values["age"] = json!(30.5);
+        assert_eq!("age: expected integer", encode::<BigInteger>(&schema(), &values).unwrap_err().message());
+        values["age"] = json!(30);
+        values["document"] = json!("birth_certificate");
+        assert_eq!(ErrorKind::InvalidInput, encode::<BigInteger>(&schema(), &values).unwrap_err().kind());
+        values["document"] = json!("passport");
+        values["issued"] = json!("yesterday");
+        assert_eq!(ErrorKind::InvalidDate, encode::<BigInteger>(&schema(), &values).unwrap_err().kind());
+        values["issued"] = json!("2018-01-26T18:30:09.453+00:00");
+        values["nickname"] = json!("Al");
+        assert_eq!("nickname: not declared in the schema", encode::<BigInteger>(&schema(), &values).unwrap_err().message());
+        values.as_object_mut().unwrap().remove("nickname");
+        values.as_object_mut().unwrap().remove("name");
+        assert_eq!("name: missing value", encode::<BigInteger>(&schema(), &values).unwrap_err().message());
+        assert!(encode::<BigInteger>(&schema(), &json!([1, 2])).is_err());
+    }
+
+    #[test]
+    fn nested_test() {
+        let schema = Schema::new(vec![
+            AttributeDescriptor::new("name", AttributeType::StringHash { algorithm: HashAlgorithm::Sha256 }, false),
+            AttributeDescriptor::new("address.postal_code", AttributeType::StringHash { algorithm: HashAlgorithm::Sha256 }, false),
+            AttributeDescriptor::new("address.moved_in", AttributeType::DateDaysSince1900, false),
+            AttributeDescriptor::new("address.unit.floor", AttributeType::Integer, true),
+        ]).unwrap();
+        let credential = json!({
+            "name": "Alice",
+            "address": {
+                "postal_code": "84101",
+                "moved_in": "2015-06-01T00:00:00Z",
+                "unit": { "floor": 3 }
+            }
+        });
+        let res = encode::<BigInteger>(&schema, &credential).unwrap();
+        assert_eq!(vec![
+            BigInteger::encode_from_utf8_as_hash::<_, sha2::Sha256>("Alice").unwrap(),
+            BigInteger::encode_from_utf8_as_hash::<_, sha2::Sha256>("84101").unwrap(),
+            BigInteger::encode_from_rfc3339_as_dayssince1900("2015-06-01T00:00:00Z").unwrap(),
+            BigInteger::encode_from_i64(3i64).unwrap(),
+        ], res);
+
+        let mut values = credential.clone();
+        values["address"]["unit"] = json!(null);
+        assert_eq!(BigInteger::encoded_null().unwrap(), encode::<BigInteger>(&schema, &values).unwrap()[3]);
+
+        let mut values = credential.clone();
+        values["address"]["moved_in"] = json!(2015);
+        assert_eq!("address.moved_in: expected date", encode::<BigInteger>(&schema, &values).unwrap_err().message());
+        let mut values = credential.clone();
+        values["address"]["moved_in"] = json!("June 2015");
+        let err = encode::<BigInteger>(&schema, &values).unwrap_err();
+        assert_eq!(ErrorKind::InvalidDate, err.kind());
+        assert!(err.message().starts_with("address.moved_in: "));
+        let mut values = credential.clone();
+        values["address"]["unit"] = json!("3B");
+        assert_eq!("address.unit: expected object", encode::<BigInteger>(&schema, &values).unwrap_err().message());
+        let mut values = credential.clone();
+        values["address"]["country"] = json!("US");
+        assert_eq!("address.country: not declared in the schema", encode::<BigInteger>(&schema, &values).unwrap_err().message());
+        let mut values = credential;
+        values["address"] = json!("123 Main St");
+        assert_eq!("address: expected object", encode::<BigInteger>(&schema, &values).unwrap_err().message());
+    }
+
+    #[test]
+    fn schema_test() {
+        let json = serde_json::to_string(&schema()).unwrap();
+        assert!(json.starts_with(r#"[{"name":"name","type":"string_hash","algorithm":"sha-256","nullable":false},"#));
+        assert!(json.contains(r#"{"name":"document","type":"enum","variants":["passport","drivers_license"],"nullable":false}"#));
+        assert_eq!(schema(), serde_json::from_str(json.as_str()).unwrap());
+
+        let res = serde_json::from_str::<Schema>(r#"[{"name":"a","type":"string_hash"},{"name":"b","type":"string_hash","algorithm":"keccak-256"}]"#).unwrap();
+        assert_eq!(AttributeType::StringHash { algorithm: HashAlgorithm::Sha256 }, res.attributes()[0].attribute_type);
+        assert_eq!(AttributeType::StringHash { algorithm: HashAlgorithm::Keccak256 }, res.attributes()[1].attribute_type);
+        assert!(serde_json::from_str::<Schema>(r#"[{"name":"a","type":"string_hash","algorithm":"md5"}]"#).is_err());
+
+        let res = serde_json::from_str::<Schema>(r#"[{"name":"age","type":"integer"},{"name":"age","type":"float"}]"#);
+        assert!(res.is_err());
+        let res = Schema::new(vec![AttributeDescriptor::new("kind", AttributeType::Enum { variants: vec![] }, false)]);
+        assert_eq!(ErrorKind::InvalidInput, res.unwrap_err().kind());
+
+        let res = Schema::new(vec![
+            AttributeDescriptor::new("address.postal_code", AttributeType::StringHash { algorithm: HashAlgorithm::Sha256 }, false),
+            AttributeDescriptor::new("address", AttributeType::StringHash { algorithm: HashAlgorithm::Sha256 }, false),
+        ]);
+        assert_eq!("address: declared as an attribute and as the object of address.postal_code", res.unwrap_err().message());
+        assert!(Schema::new(vec![
+            AttributeDescriptor::new("address", AttributeType::Bool, false),
+            AttributeDescriptor::new("address_line", AttributeType::Bool, false),
+        ]).is_ok());
+    }
+}